@@ -0,0 +1,67 @@
+//! Generates the emoji tables from the vendored Unicode `emoji-test.txt`.
+//!
+//! To update to a new Unicode emoji version, replace `data/emoji-test.txt`
+//! with the one from https://unicode.org/Public/emoji/latest/emoji-test.txt.
+
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+const EMOJI_TEST: &str = "data/emoji-test.txt";
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed={}", EMOJI_TEST);
+
+    let data = fs::read_to_string(EMOJI_TEST).expect("Couldn't read the emoji test data.");
+
+    let mut version = None;
+    let mut category = String::new();
+    let mut emojis = vec![];
+
+    for line in data.lines() {
+        if let Some(v) = line.strip_prefix("# Version:") {
+            version = Some(v.trim().to_owned());
+            continue;
+        }
+        if let Some(group) = line.strip_prefix("# group:") {
+            // e.g. "Smileys & Emotion" becomes `EmojiCategory::SmileysAndEmotion`.
+            category = group.replace('&', "And").split_whitespace().collect();
+            continue;
+        }
+        if line.starts_with('#') || line.trim().is_empty() {
+            continue;
+        }
+        // e.g. "1F44D 1F3FD ; fully-qualified # 👍🏽 E1.0 thumbs up: medium skin tone"
+        let (code_points, comment) = line.split_once('#').unwrap();
+        let code_points = code_points.split(';').next().unwrap();
+        let mut sequence = String::new();
+        for code_point in code_points.split_whitespace() {
+            write!(sequence, "\\u{{{}}}", code_point).unwrap();
+        }
+        let name = comment.trim().splitn(3, ' ').nth(2).unwrap();
+        emojis.push((sequence, name.to_owned(), category.clone()));
+    }
+
+    let mut generated = String::new();
+    writeln!(generated, "/// The version of the Unicode emoji data the emoji matcher was generated from.").unwrap();
+    writeln!(
+        generated,
+        "pub const UNICODE_EMOJI_VERSION: &str = \"{}\";",
+        version.expect("The emoji test data has no version.")
+    ).unwrap();
+    writeln!(generated, "/// Every emoji (and emoji component) sequence, in all its qualification statuses.").unwrap();
+    writeln!(generated, "pub const EMOJIS: &[EmojiData] = &[").unwrap();
+    for (sequence, name, category) in emojis {
+        writeln!(
+            generated,
+            "    EmojiData {{ sequence: \"{}\", name: {:?}, category: EmojiCategory::{} }},",
+            sequence, name, category
+        ).unwrap();
+    }
+    writeln!(generated, "];").unwrap();
+
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("emoji_data.rs");
+    fs::write(out, generated).expect("Couldn't write the generated emoji data.");
+}
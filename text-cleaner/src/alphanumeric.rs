@@ -0,0 +1,119 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::error::Result;
+use crate::script::parse_script;
+
+/// Options for removing non-alphanumeric characters.
+///
+/// The defaults remove every character that is not a word character
+/// (including whitespace), just like `Clean::remove_non_alphanumeric_characters`.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AlphanumericOptions {
+    /// Replace every run of removed characters between two words by a
+    /// single space (or a newline, if the run contained one) instead of
+    /// gluing the words together.
+    pub preserve_words: bool,
+    /// Only treat `[A-Za-z0-9]` as alphanumeric.
+    pub ascii_only: bool,
+    /// Only keep the letters of these Unicode scripts (e.g. `"Latin"`, `"Cyrillic"`).
+    /// Digits and combining marks, which belong to the `Common` and `Inherited`
+    /// scripts, are kept as well.
+    pub scripts: Option<Vec<String>>,
+}
+
+/// Removes non-alphanumeric characters according to a set of [`AlphanumericOptions`].
+#[derive(Debug, Clone)]
+pub struct AlphanumericFilter {
+    re: Regex,
+    preserve_words: bool,
+}
+
+impl AlphanumericFilter {
+    pub fn new(options: &AlphanumericOptions) -> Result<Self> {
+        let word = match options.ascii_only {
+            true => r"[0-9A-Za-z]",
+            false => r"\w",
+        };
+        let kept = match &options.scripts {
+            None => word.to_owned(),
+            Some(scripts) => {
+                let mut class = String::from(r"\p{Common}\p{Inherited}");
+                for script in scripts {
+                    class.push_str(&format!(r"\p{{Script={}}}", parse_script(script)?.full_name()));
+                }
+                format!("[{}&&[{}]]", word, class)
+            }
+        };
+        Ok(Self {
+            re: Regex::new(&format!("^{}+$", kept))?,
+            preserve_words: options.preserve_words,
+        })
+    }
+
+    /// Keep the grapheme clusters made up entirely of alphanumeric characters.
+    pub fn apply(&self, text: &str) -> String {
+        let mut result = String::with_capacity(text.len());
+        let mut separator = None;
+
+        for cluster in text.graphemes(true) {
+            if !self.re.is_match(cluster) {
+                let newline = cluster.contains('\n') || separator == Some('\n');
+                separator = Some(if newline { '\n' } else { ' ' });
+                continue;
+            }
+            if let Some(sep) = separator.take() {
+                if self.preserve_words && !result.is_empty() {
+                    result.push(sep);
+                }
+            }
+            result.push_str(cluster);
+        }
+        result
+    }
+}
+
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::error::Error;
+
+    fn apply(text: &str, options: AlphanumericOptions) -> String {
+        AlphanumericFilter::new(&options).unwrap().apply(text)
+    }
+
+    #[test]
+    pub fn test_default_glues_words() {
+        assert_eq!(apply("hello, world!", AlphanumericOptions::default()), "helloworld");
+    }
+
+    #[test]
+    pub fn test_preserve_words() {
+        let options = AlphanumericOptions { preserve_words: true, ..Default::default() };
+        assert_eq!(apply("¡hello,   world!\n-- bye --", options), "hello world\nbye");
+    }
+
+    #[test]
+    pub fn test_ascii_only() {
+        let options = AlphanumericOptions { preserve_words: true, ascii_only: true, ..Default::default() };
+        assert_eq!(apply("naïve café_2 cafe\u{0301}", options), "na ve caf 2 caf");
+    }
+
+    #[test]
+    pub fn test_scripts() {
+        let options = AlphanumericOptions {
+            preserve_words: true,
+            scripts: Some(vec![String::from("Cyrillic")]),
+            ..Default::default()
+        };
+        assert_eq!(apply("Привет hello мир 2022", options), "Привет мир 2022");
+
+        let options = AlphanumericOptions { scripts: Some(vec![String::from("Klingon")]), ..Default::default() };
+        assert!(matches!(AlphanumericFilter::new(&options), Err(Error::UnknownScript(_))));
+        let options = AlphanumericOptions { scripts: Some(vec![String::from("Latin}\\p{Greek")]), ..Default::default() };
+        assert!(matches!(AlphanumericFilter::new(&options), Err(Error::UnknownScript(_))));
+    }
+}
@@ -0,0 +1,320 @@
+use std::collections::{HashMap, HashSet};
+
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::fraction::Fraction;
+
+/// Separates the pages of a single text, as in the output of most PDF extractors.
+pub const PAGE_BREAK: char = '\x0C';
+
+/// Options for learning and removing repeated boilerplate lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BoilerplateOptions {
+    /// The share of pages a line must appear on to be boilerplate.
+    pub min_frequency: Fraction,
+    /// The fewest pages a line must appear on to be boilerplate.
+    pub min_pages: usize,
+    /// Compare lines regardless of case.
+    pub ignore_case: bool,
+    /// Compare lines regardless of their digits, so that "Page 3" and "Page 4" match.
+    pub ignore_digits: bool,
+    /// Remove page-number lines too, even if they don't repeat: the ones
+    /// spelled out as such anywhere (see [`is_page_number`]), and the ones
+    /// between dashes or brackets at the top or bottom of a page (see
+    /// [`is_decorated_page_number`]).
+    pub page_numbers: bool,
+    /// Also remove lines that are only a number, like "12", when they are the
+    /// first or last line of a page (see [`is_bare_page_number`]). Off by
+    /// default, since such lines are as often years, amounts or table cells.
+    pub bare_page_numbers: bool,
+}
+
+impl Default for BoilerplateOptions {
+    fn default() -> Self {
+        Self {
+            min_frequency: Fraction::new(0.5).unwrap(),
+            min_pages: 2,
+            ignore_case: true,
+            ignore_digits: true,
+            page_numbers: true,
+            bare_page_numbers: false,
+        }
+    }
+}
+
+/// Why a line was classified as boilerplate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BoilerplateKind {
+    Repeated,
+    PageNumber,
+}
+
+/// A line that was removed, with the page it was on and its line number
+/// on that page, both counting from 0.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RemovedLine {
+    pub page: usize,
+    pub line: usize,
+    pub text: String,
+    pub kind: BoilerplateKind,
+}
+
+/// A line learned to be boilerplate, in its normalized form, and how many pages it's on.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepeatedLine {
+    pub text: String,
+    pub pages: usize,
+}
+
+/// What was classified as boilerplate.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct BoilerplateReport {
+    pub repeated: Vec<RepeatedLine>,
+    pub removed: Vec<RemovedLine>,
+}
+
+/// Whether a line is only a page number spelled out as one, like
+/// "Page 3 of 12", "p. 4/9" or "— Page 5 —", wherever it is on its page.
+pub fn is_page_number(line: &str) -> bool {
+    lazy_static! {
+        static ref PREFIXED: Regex = Regex::new(
+            r"(?i)^\s*[-–—\[(]?\s*(?:page|pg\.?|p\.)\s*\d{1,5}(?:\s*(?:of|/)\s*\d{1,5})?\s*[-–—\])]?\s*$"
+        ).unwrap();
+    }
+    PREFIXED.is_match(line)
+}
+
+/// Whether a line is only a number between dashes or brackets, like "- 12 -",
+/// "[7]" or "(8)", which is a page number if it's at the top or bottom of a
+/// page. Elsewhere it's more likely an equation number or a reference.
+pub fn is_decorated_page_number(line: &str) -> bool {
+    lazy_static! {
+        static ref DECORATED: Regex = Regex::new(
+            r"^\s*(?:[-–—]\s*\d{1,5}\s*[-–—]|\[\s*\d{1,5}\s*\]|\(\s*\d{1,5}\s*\))\s*$"
+        ).unwrap();
+    }
+    DECORATED.is_match(line)
+}
+
+/// Whether a line is only a number, like "12" or "3/12", which is a page
+/// number if it's at the top or bottom of a page.
+pub fn is_bare_page_number(line: &str) -> bool {
+    lazy_static! {
+        static ref BARE: Regex = Regex::new(r"(?i)^\s*\d{1,5}(?:\s*(?:of|/)\s*\d{1,5})?\s*$").unwrap();
+    }
+    BARE.is_match(line)
+}
+
+/// Lines that repeat across pages.
+#[derive(Debug, Clone, Default)]
+pub struct Boilerplate {
+    options: BoilerplateOptions,
+    repeated: HashMap<String, usize>,
+}
+
+impl Boilerplate {
+    fn normalize(&self, line: &str) -> String {
+        let mut normalized = line.split_whitespace().collect::<Vec<_>>().join(" ");
+        if self.options.ignore_case {
+            normalized = normalized.to_lowercase();
+        }
+        if self.options.ignore_digits {
+            lazy_static! {
+                static ref DIGITS: Regex = Regex::new(r"\d+").unwrap();
+            }
+            normalized = DIGITS.replace_all(&normalized, "#").into_owned();
+        }
+        normalized
+    }
+
+    /// Learn the lines that appear on enough of `pages`. Blank lines are never
+    /// boilerplate, and neither are lines without letters when digits are
+    /// ignored, since those would all look alike.
+    pub fn learn<S: AsRef<str>>(pages: &[S], options: &BoilerplateOptions) -> Self {
+        let mut boilerplate = Self { options: *options, repeated: HashMap::new() };
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for page in pages {
+            let lines = page
+                .as_ref()
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .filter(|line| !options.ignore_digits || line.chars().any(char::is_alphabetic))
+                .map(|line| boilerplate.normalize(line))
+                .collect::<HashSet<_>>();
+            for line in lines {
+                *counts.entry(line).or_default() += 1;
+            }
+        }
+        let needed = ((options.min_frequency.get() * pages.len() as f64).ceil() as usize).max(options.min_pages).max(2);
+        counts.retain(|_, count| *count >= needed);
+        boilerplate.repeated = counts;
+        boilerplate
+    }
+
+    /// Why `line` is boilerplate, if it is. `edge` tells whether it's the
+    /// first or last line of its page that isn't blank.
+    fn classify(&self, line: &str, edge: bool) -> Option<BoilerplateKind> {
+        if line.trim().is_empty() {
+            None
+        } else if self.repeated.contains_key(&self.normalize(line)) {
+            Some(BoilerplateKind::Repeated)
+        } else if self.options.page_numbers && (is_page_number(line) || edge && is_decorated_page_number(line))
+            || self.options.bare_page_numbers && edge && is_bare_page_number(line)
+        {
+            Some(BoilerplateKind::PageNumber)
+        } else {
+            None
+        }
+    }
+
+    /// Remove the boilerplate lines of a page, recording them in `report` as being on page `index`.
+    fn remove_from(&self, page: &str, index: usize, report: &mut BoilerplateReport) -> String {
+        let lines = page.split('\n').collect::<Vec<_>>();
+        let filled = |line: &&str| !line.trim().is_empty();
+        let first = lines.iter().position(filled);
+        let last = lines.iter().rposition(filled);
+        let mut kept = vec![];
+        for (number, line) in lines.into_iter().enumerate() {
+            let edge = first == Some(number) || last == Some(number);
+            match self.classify(line.trim_end_matches('\r'), edge) {
+                Some(kind) => report.removed.push(RemovedLine { page: index, line: number, text: line.to_owned(), kind }),
+                None => kept.push(line),
+            }
+        }
+        kept.join("\n")
+    }
+
+    pub fn remove(&self, page: &str) -> String {
+        self.remove_from(page, 0, &mut BoilerplateReport::default())
+    }
+
+    /// Remove the boilerplate lines of every page, along with a report of what was removed.
+    pub fn remove_all<S: AsRef<str>>(&self, pages: &[S]) -> (Vec<String>, BoilerplateReport) {
+        let mut report = BoilerplateReport::default();
+        let mut repeated = self
+            .repeated
+            .iter()
+            .map(|(text, pages)| RepeatedLine { text: text.clone(), pages: *pages })
+            .collect::<Vec<_>>();
+        repeated.sort_by(|a, b| b.pages.cmp(&a.pages).then_with(|| a.text.cmp(&b.text)));
+        report.repeated = repeated;
+
+        let cleaned = pages
+            .iter()
+            .enumerate()
+            .map(|(index, page)| self.remove_from(page.as_ref(), index, &mut report))
+            .collect();
+        (cleaned, report)
+    }
+}
+
+/// Learn and remove the boilerplate of a set of pages in one go.
+pub fn remove_boilerplate<S: AsRef<str>>(pages: &[S], options: &BoilerplateOptions) -> (Vec<String>, BoilerplateReport) {
+    Boilerplate::learn(pages, options).remove_all(pages)
+}
+
+/// Remove the boilerplate of a single text whose pages are separated by [`PAGE_BREAK`]s.
+pub fn remove_paged_boilerplate(text: &str, options: &BoilerplateOptions) -> String {
+    let pages = text.split(PAGE_BREAK).collect::<Vec<_>>();
+    let (cleaned, _) = remove_boilerplate(&pages, options);
+    cleaned.join(&PAGE_BREAK.to_string())
+}
+
+/// Remove the lines that are page numbers marked as such (see
+/// [`BoilerplateOptions::page_numbers`]), on every page of a text whose
+/// pages are separated by [`PAGE_BREAK`]s.
+pub fn remove_page_numbers(text: &str) -> String {
+    let boilerplate = Boilerplate::default();
+    text.split(PAGE_BREAK)
+        .map(|page| boilerplate.remove(page))
+        .collect::<Vec<_>>()
+        .join(&PAGE_BREAK.to_string())
+}
+
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    fn pages() -> Vec<String> {
+        ["Revenue grew.", "Costs fell.", "Staff doubled.", "Outlook is good."]
+            .iter()
+            .enumerate()
+            .map(|(i, content)| format!(
+                "ACME Corp  Annual Report\nWe use cookies. Accept?\n{}\n\nPage {} of 4",
+                content, i + 1
+            ))
+            .collect()
+    }
+
+    #[test]
+    pub fn test_is_page_number() {
+        for line in ["Page 3 of 12", "p. 4/9", "PAGE 2", "— Page 5 —"] {
+            assert!(is_page_number(line), "{}", line);
+        }
+        for line in ["12", "2023", "12 apples", "Chapter 3", "Page three", "-5", "(8)", ""] {
+            assert!(!is_page_number(line), "{}", line);
+        }
+        for line in [" - 3 - ", "[7]", "(8)"] {
+            assert!(is_decorated_page_number(line), "{}", line);
+        }
+        assert!(!is_decorated_page_number("-5") && !is_decorated_page_number("(8) a"));
+        assert!(is_bare_page_number("12") && is_bare_page_number(" 3 / 12 "));
+        assert!(!is_bare_page_number("1500 EUR"));
+    }
+
+    #[test]
+    pub fn test_bare_page_numbers() {
+        let pages = ["Total:\n2023\n1500\n\n7\n", "8\nText\n1999\nmore"];
+        let (cleaned, _) = remove_boilerplate(&pages, &BoilerplateOptions::default());
+        assert_eq!(cleaned, pages);
+
+        let options = BoilerplateOptions { bare_page_numbers: true, ..Default::default() };
+        let (cleaned, report) = remove_boilerplate(&pages, &options);
+        assert_eq!(cleaned, ["Total:\n2023\n1500\n\n", "Text\n1999\nmore"]);
+        assert!(report.removed.iter().all(|line| line.kind == BoilerplateKind::PageNumber));
+        assert_eq!(remove_page_numbers("Total:\n2023\n1500"), "Total:\n2023\n1500");
+    }
+
+    #[test]
+    pub fn test_remove_repeated_lines() {
+        let (cleaned, report) = remove_boilerplate(&pages(), &BoilerplateOptions::default());
+        assert_eq!(cleaned[0], "Revenue grew.\n");
+        assert_eq!(
+            report.repeated.iter().map(|line| line.text.as_str()).collect::<Vec<_>>(),
+            vec!["acme corp annual report", "page # of #", "we use cookies. accept?"]
+        );
+        assert_eq!(report.removed.len(), 12);
+        assert_eq!(report.removed[2], RemovedLine { page: 0, line: 4, text: String::from("Page 1 of 4"), kind: BoilerplateKind::Repeated });
+    }
+
+    #[test]
+    pub fn test_frequency_threshold() {
+        let mut pages = pages();
+        pages[0] = pages[0].replace("Accept?", "Really?");
+        pages[1] = pages[1].replace("Accept?", "Really?");
+        let options = BoilerplateOptions { min_frequency: Fraction::new(0.75).unwrap(), ignore_digits: false, ..Default::default() };
+        let (cleaned, report) = remove_boilerplate(&pages, &options);
+        assert_eq!(cleaned[0], "We use cookies. Really?\nRevenue grew.\n");
+        assert!(report.removed.iter().any(|line| line.kind == BoilerplateKind::PageNumber));
+    }
+
+    #[test]
+    pub fn test_paged_text() {
+        let text = pages().join("\x0C");
+        let cleaned = remove_paged_boilerplate(&text, &BoilerplateOptions::default());
+        assert_eq!(cleaned.split(PAGE_BREAK).count(), 4);
+        assert!(!cleaned.contains("cookies"));
+        assert_eq!(remove_page_numbers("text\nmore\n- 2 -"), "text\nmore");
+    }
+
+    #[test]
+    pub fn test_decorated_numbers_mid_page() {
+        let text = "[3]\nE = mc^2\n(8)\nas shown in\n[7]\nPage 3\nend\n(4)";
+        assert_eq!(remove_page_numbers(text), "E = mc^2\n(8)\nas shown in\n[7]\nend");
+    }
+}
@@ -0,0 +1,282 @@
+use hashbrown::HashMap;
+use serde::{Deserialize, Serialize};
+use unicode_normalization::char::canonical_combining_class;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::segment::{SegmentOptions, Segmenter};
+
+/// Locales whose casing differs from the language-neutral Unicode mappings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CaseLocale {
+    /// Dotted and dotless I are separate letters: `i`/`İ` and `ı`/`I`.
+    #[serde(rename = "tr")]
+    Turkish,
+    #[serde(rename = "az")]
+    Azeri,
+    /// Lowercase `i` keeps its dot under accents: `Í` becomes `i̇́`.
+    #[serde(rename = "lt")]
+    Lithuanian,
+}
+
+/// A change of case.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Case {
+    #[default]
+    Lower,
+    Upper,
+    /// Unicode full case folding, for comparing text regardless of case.
+    Fold,
+    /// A capital at the start of every word.
+    Title,
+    /// A capital at the start of every sentence, the rest in lowercase.
+    Sentence,
+    /// Sentence case for the sentences written in all capitals, keeping `acronyms`.
+    DeShout,
+}
+
+/// Options for changing the case of text.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CaseOptions {
+    pub case: Case,
+    pub locale: Option<CaseLocale>,
+    /// Words kept as they are written here when sentences are de-shouted,
+    /// like `"NASA"` or `"PhD"`.
+    pub acronyms: Vec<String>,
+}
+
+fn is_turkic(locale: Option<CaseLocale>) -> bool {
+    matches!(locale, Some(CaseLocale::Turkish | CaseLocale::Azeri))
+}
+
+/// Whether `c` is a combining mark that sits above the letter before it.
+fn is_above(c: char) -> bool {
+    canonical_combining_class(c) == 230
+}
+
+pub fn lowercase(text: &str, locale: Option<CaseLocale>) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (locale, c) {
+            (Some(CaseLocale::Turkish | CaseLocale::Azeri), 'İ') => result.push('i'),
+            (Some(CaseLocale::Turkish | CaseLocale::Azeri), 'I') => match chars.next_if_eq(&'\u{307}') {
+                Some(_) => result.push('i'),
+                None => result.push('ı'),
+            },
+            (Some(CaseLocale::Lithuanian), 'Ì') => result.push_str("i\u{307}\u{300}"),
+            (Some(CaseLocale::Lithuanian), 'Í') => result.push_str("i\u{307}\u{301}"),
+            (Some(CaseLocale::Lithuanian), 'Ĩ') => result.push_str("i\u{307}\u{303}"),
+            (Some(CaseLocale::Lithuanian), 'I' | 'J' | 'Į') if chars.peek().is_some_and(|next| is_above(*next)) => {
+                result.extend(c.to_lowercase());
+                result.push('\u{307}');
+            },
+            _ => result.push(c),
+        }
+    }
+    // The rest goes through `str::to_lowercase`, which knows about final sigma.
+    result.to_lowercase()
+}
+
+pub fn uppercase(text: &str, locale: Option<CaseLocale>) -> String {
+    match locale {
+        Some(CaseLocale::Turkish | CaseLocale::Azeri) => text.replace('i', "İ").to_uppercase(),
+        Some(CaseLocale::Lithuanian) => {
+            let mut result = String::with_capacity(text.len());
+            let mut soft_dotted = false;
+            for c in text.chars() {
+                if c == '\u{307}' && soft_dotted {
+                    continue;
+                }
+                if !is_above(c) {
+                    soft_dotted = matches!(c, 'i' | 'j' | 'į' | 'ɨ');
+                }
+                result.push(c);
+            }
+            result.to_uppercase()
+        },
+        None => text.to_uppercase(),
+    }
+}
+
+/// Unicode full case folding, so that `"Straße"` and `"STRASSE"` fold the same.
+pub fn case_fold(text: &str, locale: Option<CaseLocale>) -> String {
+    match is_turkic(locale) {
+        true => caseless::default_case_fold_str(&text.replace('I', "ı").replace('İ', "i")),
+        false => caseless::default_case_fold_str(text),
+    }
+}
+
+/// `word` with its first letter in title case and the rest in lowercase.
+fn capitalize(word: &str, locale: Option<CaseLocale>) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => {
+            let first = match first {
+                // The digraphs have title case forms of their own.
+                'Ǆ' | 'ǅ' | 'ǆ' => String::from("ǅ"),
+                'Ǉ' | 'ǈ' | 'ǉ' => String::from("ǈ"),
+                'Ǌ' | 'ǋ' | 'ǌ' => String::from("ǋ"),
+                'Ǳ' | 'ǲ' | 'ǳ' => String::from("ǲ"),
+                _ => uppercase(first.encode_utf8(&mut [0; 4]), locale),
+            };
+            first + &lowercase(chars.as_str(), locale)
+        },
+        None => String::new(),
+    }
+}
+
+fn is_word(segment: &str) -> bool {
+    segment.chars().any(char::is_alphabetic)
+}
+
+/// Whether a sentence is written in capitals, and long enough to tell.
+fn is_shouting(sentence: &str) -> bool {
+    let letters = sentence.chars().filter(|c| c.is_alphabetic()).collect::<Vec<_>>();
+    letters.len() >= 4 && letters.iter().all(|c| !c.is_lowercase()) && letters.iter().any(|c| c.is_uppercase())
+}
+
+/// Changes the case of text.
+#[derive(Debug, Clone)]
+pub struct CaseConverter {
+    case: Case,
+    locale: Option<CaseLocale>,
+    acronyms: HashMap<String, String>,
+    segmenter: Segmenter,
+}
+
+impl CaseConverter {
+    pub fn new(options: &CaseOptions) -> Self {
+        Self {
+            case: options.case,
+            locale: options.locale,
+            acronyms: options.acronyms.iter().map(|acronym| (case_fold(acronym, options.locale), acronym.clone())).collect(),
+            segmenter: Segmenter::new(&SegmentOptions::default()),
+        }
+    }
+
+    pub fn title_case(&self, text: &str) -> String {
+        text.split_word_bounds()
+            .map(|segment| match is_word(segment) {
+                true => capitalize(segment, self.locale),
+                false => segment.to_owned(),
+            })
+            .collect()
+    }
+
+    /// A sentence in lowercase but for its first word, and the acronyms
+    /// when `keep_acronyms` is set.
+    fn sentence_case_of(&self, sentence: &str, keep_acronyms: bool) -> String {
+        let mut first = true;
+        let mut result = String::with_capacity(sentence.len());
+        for segment in sentence.split_word_bounds() {
+            if !is_word(segment) {
+                result.push_str(segment);
+                continue;
+            }
+            let acronym = keep_acronyms.then(|| self.acronyms.get(&case_fold(segment, self.locale))).flatten();
+            match (acronym, first) {
+                (Some(acronym), _) => result.push_str(acronym),
+                (None, true) => result.push_str(&capitalize(segment, self.locale)),
+                (None, false) => result.push_str(&lowercase(segment, self.locale)),
+            }
+            first = false;
+        }
+        result
+    }
+
+    /// Rewrite the sentences of `text` that `rewrite` returns a new version
+    /// of, keeping the text between them as it is.
+    fn map_sentences<F>(&self, text: &str, rewrite: F) -> String
+    where
+        F: Fn(&str) -> Option<String>
+    {
+        let mut result = String::with_capacity(text.len());
+        let mut last = 0;
+        for sentence in self.segmenter.sentences(text) {
+            result.push_str(&text[last..sentence.start]);
+            result.push_str(&rewrite(&sentence.text).unwrap_or(sentence.text));
+            last = sentence.end;
+        }
+        result.push_str(&text[last..]);
+        result
+    }
+
+    pub fn sentence_case(&self, text: &str) -> String {
+        self.map_sentences(text, |sentence| Some(self.sentence_case_of(sentence, false)))
+    }
+
+    /// Sentence case for the sentences in all capitals, keeping the acronyms.
+    pub fn de_shout(&self, text: &str) -> String {
+        self.map_sentences(text, |sentence| is_shouting(sentence).then(|| self.sentence_case_of(sentence, true)))
+    }
+
+    pub fn apply(&self, text: &str) -> String {
+        match self.case {
+            Case::Lower => lowercase(text, self.locale),
+            Case::Upper => uppercase(text, self.locale),
+            Case::Fold => case_fold(text, self.locale),
+            Case::Title => self.title_case(text),
+            Case::Sentence => self.sentence_case(text),
+            Case::DeShout => self.de_shout(text),
+        }
+    }
+}
+
+pub fn change_case(text: &str, options: &CaseOptions) -> String {
+    CaseConverter::new(options).apply(text)
+}
+
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    fn converter(case: Case, locale: Option<CaseLocale>) -> CaseConverter {
+        CaseConverter::new(&CaseOptions { case, locale, ..Default::default() })
+    }
+
+    #[test]
+    pub fn test_locale_casing() {
+        assert_eq!(lowercase("DİYARBAKIR", Some(CaseLocale::Turkish)), "diyarbakır");
+        assert_eq!(lowercase("DİYARBAKIR", None), "di\u{307}yarbakir");
+        assert_eq!(uppercase("istanbul ılık", Some(CaseLocale::Azeri)), "İSTANBUL ILIK");
+        assert_eq!(uppercase("istanbul", None), "ISTANBUL");
+
+        assert_eq!(lowercase("ÍI\u{300}", Some(CaseLocale::Lithuanian)), "i\u{307}\u{301}i\u{307}\u{300}");
+        assert_eq!(uppercase("i\u{307}\u{301}", Some(CaseLocale::Lithuanian)), "I\u{301}");
+        assert_eq!(lowercase("ΟΔΟΣ", None), "οδος");
+    }
+
+    #[test]
+    pub fn test_case_fold() {
+        assert_eq!(case_fold("Straße", None), case_fold("STRASSE", None));
+        assert_eq!(case_fold("ΣΊΣΥΦΟΣ", None), case_fold("σίσυφος", None));
+        assert_eq!(case_fold("KIZ", Some(CaseLocale::Turkish)), "kız");
+    }
+
+    #[test]
+    pub fn test_title_and_sentence_case() {
+        let title = converter(Case::Title, None);
+        assert_eq!(title.apply("the QUICK brown-fox's ǆungla"), "The Quick Brown-Fox's ǅungla");
+        assert_eq!(converter(Case::Title, Some(CaseLocale::Turkish)).apply("izmir İLİ"), "İzmir İli");
+
+        let sentence = converter(Case::Sentence, None);
+        assert_eq!(sentence.apply("hELLO THERE. Dr. WHO? yes!\n\nnew PARAGRAPH"), "Hello there. Dr. who? Yes!\n\nNew paragraph");
+    }
+
+    #[test]
+    pub fn test_de_shout() {
+        let options = CaseOptions {
+            case: Case::DeShout,
+            acronyms: vec![String::from("NASA"), String::from("PhD")],
+            ..Default::default()
+        };
+        let text = "Read this. NASA LAUNCHED A NEW ROCKET TODAY! IT WAS FUN, SAID A PHD. Ok.";
+        assert_eq!(
+            change_case(text, &options),
+            "Read this. NASA launched a new rocket today! It was fun, said a PhD. Ok."
+        );
+    }
+}
@@ -0,0 +1,283 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use aho_corasick::{AhoCorasick, MatchKind};
+use hashbrown::HashSet;
+use serde::{Deserialize, Serialize};
+
+use crate::case::case_fold;
+use crate::error::{Error, Result};
+
+/// How the keys of a [`Dictionary`] are matched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct DictionaryOptions {
+    /// Only replace keys that are not part of a longer word.
+    #[serde(default)]
+    pub whole_word: bool,
+    /// Match keys regardless of case, by Unicode full case folding.
+    #[serde(default)]
+    pub case_insensitive: bool,
+}
+
+/// A pipeline step that replaces the keys of a dictionary by their values.
+///
+/// The entries are read from `path` (a `.tsv` or `.json` file) and/or given inline.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct DictionaryRule {
+    #[serde(default)]
+    pub path: Option<PathBuf>,
+    #[serde(default)]
+    pub entries: BTreeMap<String, String>,
+    #[serde(flatten)]
+    pub options: DictionaryOptions,
+}
+
+impl DictionaryRule {
+    pub fn compile(&self) -> Result<Dictionary> {
+        let mut entries = match &self.path {
+            Some(path) => read_entries(path)?,
+            None => vec![],
+        };
+        entries.extend(self.entries.iter().map(|(k, v)| (k.clone(), v.clone())));
+        Dictionary::new(entries, self.options)
+    }
+}
+
+/// Parse `key<TAB>value` lines. Empty lines are skipped, and so are comments:
+/// lines that are only `#` or start with `# `. Other lines starting with `#`,
+/// like `#hashtag<TAB>tag`, are entries. Keys that start with `# ` can be
+/// given in a `.json` dictionary instead.
+pub fn parse_tsv(tsv: &str) -> Result<Vec<(String, String)>> {
+    let mut entries = vec![];
+    for (idx, line) in tsv.lines().enumerate() {
+        if line.trim().is_empty() || line == "#" || line.starts_with("# ") {
+            continue;
+        }
+        match line.split_once('\t') {
+            Some((key, value)) => entries.push((key.to_owned(), value.to_owned())),
+            None => return Err(Error::InvalidDictionary {
+                line: idx + 1,
+                reason: String::from("expected a tab between the key and its replacement"),
+            })
+        }
+    }
+    Ok(entries)
+}
+
+/// Parse a JSON object whose keys are replaced by their (string) values.
+pub fn parse_json(json: &str) -> Result<Vec<(String, String)>> {
+    let map: BTreeMap<String, String> = serde_json::from_str(json)
+        .map_err(|e| Error::InvalidDictionary { line: e.line(), reason: e.to_string() })?;
+    Ok(map.into_iter().collect())
+}
+
+/// Read dictionary entries from a `.json` file, or a `.tsv` file otherwise.
+pub fn read_entries(path: &Path) -> Result<Vec<(String, String)>> {
+    let contents = std::fs::read_to_string(path)?;
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("json") => parse_json(&contents),
+        _ => parse_tsv(&contents),
+    }
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Case fold `text`, keeping track of where it came from in the original:
+/// the offset of each folded byte that starts the folding of an original
+/// char, and `None` for the bytes inside one (like the second `s` of `ß`).
+fn fold_case(text: &str) -> (String, Vec<Option<usize>>) {
+    let mut folded = String::with_capacity(text.len());
+    let mut offsets = Vec::with_capacity(text.len() + 1);
+    for (idx, c) in text.char_indices() {
+        folded.push_str(&case_fold(c.encode_utf8(&mut [0; 4]), None));
+        offsets.push(Some(idx));
+        offsets.resize(folded.len(), None);
+    }
+    offsets.push(Some(text.len()));
+    (folded, offsets)
+}
+
+/// A set of substitutions that are all matched in a single pass
+/// with leftmost-longest semantics.
+#[derive(Debug, Clone)]
+pub struct Dictionary {
+    automaton: AhoCorasick,
+    replacements: Vec<String>,
+    options: DictionaryOptions,
+}
+
+impl Dictionary {
+    /// Build a dictionary. When a key occurs more than once, the first one wins.
+    pub fn new<I>(entries: I, options: DictionaryOptions) -> Result<Self>
+    where
+        I: IntoIterator<Item = (String, String)>
+    {
+        let mut seen = HashSet::new();
+        let mut keys = vec![];
+        let mut replacements = vec![];
+
+        for (key, value) in entries {
+            let key = match options.case_insensitive {
+                true => case_fold(&key, None),
+                false => key,
+            };
+            if key.is_empty() || !seen.insert(key.clone()) {
+                continue;
+            }
+            keys.push(key);
+            replacements.push(value);
+        }
+
+        // Whole-word matching needs to see every candidate at a position,
+        // not just the longest, since the longest may end inside a word.
+        let kind = match options.whole_word {
+            true => MatchKind::Standard,
+            false => MatchKind::LeftmostLongest,
+        };
+        let automaton = AhoCorasick::builder().match_kind(kind).build(&keys)?;
+
+        Ok(Self { automaton, replacements, options })
+    }
+
+    pub fn from_tsv(tsv: &str, options: DictionaryOptions) -> Result<Self> {
+        Self::new(parse_tsv(tsv)?, options)
+    }
+
+    pub fn from_json(json: &str, options: DictionaryOptions) -> Result<Self> {
+        Self::new(parse_json(json)?, options)
+    }
+
+    pub fn from_path<P: AsRef<Path>>(path: P, options: DictionaryOptions) -> Result<Self> {
+        Self::new(read_entries(path.as_ref())?, options)
+    }
+
+    pub fn len(&self) -> usize {
+        self.replacements.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.replacements.is_empty()
+    }
+
+    /// The non-overlapping matches in `haystack` as `(start, end, entry)`.
+    fn find(&self, haystack: &str) -> Vec<(usize, usize, usize)> {
+        if !self.options.whole_word {
+            return self
+                .automaton
+                .find_iter(haystack)
+                .map(|m| (m.start(), m.end(), m.pattern().as_usize()))
+                .collect();
+        }
+
+        let mut candidates = self
+            .automaton
+            .find_overlapping_iter(haystack)
+            .filter(|m| self.is_whole_word(haystack, m.start(), m.end()))
+            .map(|m| (m.start(), m.end(), m.pattern().as_usize()))
+            .collect::<Vec<_>>();
+        candidates.sort_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)));
+
+        let mut matches = vec![];
+        let mut last_end = 0;
+        for candidate in candidates {
+            if candidate.0 >= last_end {
+                last_end = candidate.1;
+                matches.push(candidate);
+            }
+        }
+        matches
+    }
+
+    fn is_whole_word(&self, haystack: &str, start: usize, end: usize) -> bool {
+        let matched = &haystack[start..end];
+        let starts_word = matched.chars().next().is_some_and(is_word_char);
+        let ends_word = matched.chars().next_back().is_some_and(is_word_char);
+
+        let before = haystack[..start].chars().next_back().is_some_and(is_word_char);
+        let after = haystack[end..].chars().next().is_some_and(is_word_char);
+
+        !(starts_word && before || ends_word && after)
+    }
+
+    /// Replace every occurrence of a key in `text` by its value.
+    pub fn replace(&self, text: &str) -> String {
+        let (haystack, offsets) = match self.options.case_insensitive {
+            true => fold_case(text),
+            false => (String::new(), vec![]),
+        };
+        let matches = match self.options.case_insensitive {
+            true => self
+                .find(&haystack)
+                .into_iter()
+                .filter_map(|(start, end, idx)| Some((offsets[start]?, offsets[end]?, idx)))
+                .collect(),
+            false => self.find(text),
+        };
+
+        let mut result = String::with_capacity(text.len());
+        let mut last = 0;
+        for (start, end, idx) in matches {
+            result.push_str(&text[last..start]);
+            result.push_str(&self.replacements[idx]);
+            last = end;
+        }
+        result.push_str(&text[last..]);
+        result
+    }
+}
+
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    fn entries(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    pub fn test_leftmost_longest() {
+        let dict = Dictionary::new(
+            entries(&[("New", "Old"), ("New York", "NYC"), ("York", "Yorkshire")]),
+            DictionaryOptions::default()
+        ).unwrap();
+        assert_eq!(dict.replace("New York and New Jersey"), "NYC and Old Jersey");
+    }
+
+    #[test]
+    pub fn test_whole_word() {
+        let options = DictionaryOptions { whole_word: true, ..Default::default() };
+        let dict = Dictionary::new(entries(&[("cat", "dog"), ("cat food", "kibble")]), options).unwrap();
+        assert_eq!(dict.replace("cat category cat foods cat food"), "dog category dog foods kibble");
+    }
+
+    #[test]
+    pub fn test_case_insensitive() {
+        let options = DictionaryOptions { case_insensitive: true, whole_word: true };
+        let dict = Dictionary::new(entries(&[("ÉTÉ", "summer"), ("js", "JavaScript")]), options).unwrap();
+        assert_eq!(dict.replace("L'été, JS and jsx"), "L'summer, JavaScript and jsx");
+
+        let options = DictionaryOptions { case_insensitive: true, whole_word: false };
+        let dict = Dictionary::new(entries(&[("straße", "street"), ("s", "z")]), options).unwrap();
+        assert_eq!(dict.replace("STRASSE, Straße, Maß"), "street, street, Maß");
+    }
+
+    #[test]
+    pub fn test_parse_tsv() {
+        let dict = Dictionary::from_tsv("# brands\n#\nacme\tACME Corp.\n\nfoo\tbar\n#hashtag\ttag\n", DictionaryOptions::default()).unwrap();
+        assert_eq!(dict.len(), 3);
+        assert_eq!(dict.replace("acme foo #hashtag"), "ACME Corp. bar tag");
+        assert!(matches!(
+            parse_tsv("ok\tfine\nbroken"),
+            Err(Error::InvalidDictionary { line: 2, .. })
+        ));
+    }
+
+    #[test]
+    pub fn test_parse_json() {
+        let dict = Dictionary::from_json(r#"{"e.g.": "for example"}"#, DictionaryOptions::default()).unwrap();
+        assert_eq!(dict.replace("fruit, e.g. apples"), "fruit, for example apples");
+    }
+}
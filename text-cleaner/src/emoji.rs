@@ -0,0 +1,204 @@
+use std::ops::Range;
+
+use aho_corasick::{AhoCorasick, MatchKind};
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+
+/// The groups that emojis are organized in by Unicode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmojiCategory {
+    SmileysAndEmotion,
+    PeopleAndBody,
+    Component,
+    AnimalsAndNature,
+    FoodAndDrink,
+    TravelAndPlaces,
+    Activities,
+    Objects,
+    Symbols,
+    Flags,
+}
+
+/// An emoji sequence along with its CLDR short name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EmojiData {
+    pub sequence: &'static str,
+    pub name: &'static str,
+    pub category: EmojiCategory,
+}
+
+// Generated by `build.rs` from `data/emoji-test.txt`.
+include!(concat!(env!("OUT_DIR"), "/emoji_data.rs"));
+
+lazy_static! {
+    static ref MATCHER: AhoCorasick = AhoCorasick::builder()
+        .match_kind(MatchKind::LeftmostLongest)
+        .build(EMOJIS.iter().map(|emoji| emoji.sequence))
+        .unwrap();
+}
+
+/// The emojis in `text` along with their byte ranges. A sequence like a ZWJ
+/// family, a flag or a keycap is a single match.
+pub fn find_emojis(text: &str) -> impl Iterator<Item = (Range<usize>, &'static EmojiData)> + '_ {
+    MATCHER
+        .find_iter(text)
+        .map(|m| (m.range(), &EMOJIS[m.pattern().as_usize()]))
+}
+
+pub fn remove_emojis(text: &str) -> String {
+    replace_emojis(text, &EmojiOptions::default())
+}
+
+/// What to do with an emoji.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmojiAction {
+    #[default]
+    Remove,
+    /// Replace it by its short name as a shortcode, e.g. `:face_with_tears_of_joy:`.
+    ShortCode,
+    /// Replace it by its short name, e.g. `face with tears of joy`.
+    Description,
+    /// Only remove its skin tone modifiers.
+    StripSkinTones,
+}
+
+/// Options for handling emojis.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EmojiOptions {
+    pub action: EmojiAction,
+    /// Only handle the emojis of these categories. When unset, all emojis are handled.
+    pub only: Option<Vec<EmojiCategory>>,
+    /// Leave the emojis of these categories untouched.
+    pub except: Vec<EmojiCategory>,
+}
+
+impl EmojiOptions {
+    fn handles(&self, category: EmojiCategory) -> bool {
+        let included = self.only.as_ref().is_none_or(|only| only.contains(&category));
+        included && !self.except.contains(&category)
+    }
+}
+
+/// The shortcode for a short name, e.g. `:flag_aland_islands:` for "flag: Åland Islands".
+pub fn short_code(name: &str) -> String {
+    let mut code = String::from(":");
+    for c in deunicode::deunicode(name).to_lowercase().chars() {
+        match c.is_ascii_alphanumeric() || c == '#' || c == '*' {
+            true => code.push(c),
+            false => {
+                if !code.ends_with([':', '_']) {
+                    code.push('_');
+                }
+            }
+        }
+    }
+    if code.ends_with('_') {
+        code.pop();
+    }
+    code.push(':');
+    code
+}
+
+fn is_skin_tone(c: char) -> bool {
+    ('\u{1F3FB}'..='\u{1F3FF}').contains(&c)
+}
+
+/// Push a word in place of an emoji, making sure it doesn't run into its neighbours.
+fn push_word(result: &mut String, word: &str, next: Option<char>) {
+    if result.chars().next_back().is_some_and(|c| !c.is_whitespace()) {
+        result.push(' ');
+    }
+    result.push_str(word);
+    if next.is_some_and(|c| !c.is_whitespace()) {
+        result.push(' ');
+    }
+}
+
+pub fn replace_emojis(text: &str, options: &EmojiOptions) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut last = 0;
+    for (range, emoji) in find_emojis(text) {
+        result.push_str(&text[last..range.start]);
+        last = range.end;
+
+        if !options.handles(emoji.category) {
+            result.push_str(&text[range]);
+            continue;
+        }
+        let next = text[range.end..].chars().next();
+        match options.action {
+            EmojiAction::Remove => {},
+            EmojiAction::ShortCode => push_word(&mut result, &short_code(emoji.name), next),
+            EmojiAction::Description => push_word(&mut result, emoji.name, next),
+            EmojiAction::StripSkinTones => result.extend(text[range].chars().filter(|c| !is_skin_tone(*c))),
+        }
+    }
+    result.push_str(&text[last..]);
+    result
+}
+
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    fn with_action(action: EmojiAction) -> EmojiOptions {
+        EmojiOptions { action, ..Default::default() }
+    }
+
+    #[test]
+    pub fn test_unicode_emoji_version() {
+        assert_eq!(UNICODE_EMOJI_VERSION, "17.0");
+    }
+
+    #[test]
+    pub fn test_zwj_sequences() {
+        let s = "a\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}\u{200D}\u{1F466}b\u{1F469}\u{1F3FD}\u{200D}\u{1F4BB}c";
+        assert_eq!(find_emojis(s).count(), 2);
+        assert_eq!(remove_emojis(s), "abc");
+    }
+
+    #[test]
+    pub fn test_skin_tones() {
+        let (range, emoji) = find_emojis("\u{1F44D}\u{1F3FF}").next().unwrap();
+        assert_eq!(range, 0..8);
+        assert_eq!(emoji.name, "thumbs up: dark skin tone");
+        assert_eq!(remove_emojis("ok \u{1F44D}\u{1F3FD}!"), "ok !");
+    }
+
+    #[test]
+    pub fn test_flags() {
+        assert_eq!(remove_emojis("\u{1F1E8}\u{1F1E6} eh \u{1F3F4}\u{E0067}\u{E0062}\u{E0073}\u{E0063}\u{E0074}\u{E007F}"), " eh ");
+    }
+
+    #[test]
+    pub fn test_keycaps() {
+        assert_eq!(remove_emojis("#\u{FE0F}\u{20E3}1\u{20E3} #1"), " #1");
+    }
+
+    #[test]
+    pub fn test_short_codes_and_descriptions() {
+        let s = "lol😂 so good 🇦🇽";
+        assert_eq!(replace_emojis(s, &with_action(EmojiAction::ShortCode)), "lol :face_with_tears_of_joy: so good :flag_aland_islands:");
+        assert_eq!(replace_emojis(s, &with_action(EmojiAction::Description)), "lol face with tears of joy so good flag: Åland Islands");
+        assert_eq!(short_code("keycap: #"), ":keycap_#:");
+    }
+
+    #[test]
+    pub fn test_categories() {
+        let options = EmojiOptions { except: vec![EmojiCategory::Flags], ..Default::default() };
+        assert_eq!(replace_emojis("😀🇨🇦🍕", &options), "🇨🇦");
+
+        let options = EmojiOptions { only: Some(vec![EmojiCategory::FoodAndDrink]), ..Default::default() };
+        assert_eq!(replace_emojis("😀🇨🇦🍕", &options), "😀🇨🇦");
+    }
+
+    #[test]
+    pub fn test_strip_skin_tones() {
+        let s = "\u{1F44D}\u{1F3FD} \u{1F469}\u{1F3FF}\u{200D}\u{1F4BB}";
+        assert_eq!(replace_emojis(s, &with_action(EmojiAction::StripSkinTones)), "\u{1F44D} \u{1F469}\u{200D}\u{1F4BB}");
+    }
+}
@@ -0,0 +1,290 @@
+use std::ops::Range;
+
+use aho_corasick::{AhoCorasick, MatchKind};
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// Whether a match is an ASCII emoticon (like `:-)`) or a kaomoji (like `(^_^)`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmoticonKind {
+    Emoticon,
+    Kaomoji,
+}
+
+/// A detected emoticon or kaomoji.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Emoticon {
+    pub kind: EmoticonKind,
+    /// A short name describing it, e.g. "smile" or "table_flip".
+    pub name: &'static str,
+    /// The closest emoji, if there is one.
+    pub emoji: Option<&'static str>,
+}
+
+/// ASCII emoticons as `(emoticon, name, emoji)`.
+const EMOTICONS: &[(&str, &str, &str)] = &[
+    (":)", "smile", "🙂"),
+    (":-)", "smile", "🙂"),
+    ("=)", "smile", "🙂"),
+    (":]", "smile", "🙂"),
+    (":D", "grin", "😄"),
+    (":-D", "grin", "😄"),
+    ("=D", "grin", "😄"),
+    ("xD", "laugh", "😆"),
+    ("XD", "laugh", "😆"),
+    (";)", "wink", "😉"),
+    (";-)", "wink", "😉"),
+    (";D", "wink", "😉"),
+    (":P", "tongue", "😛"),
+    (":-P", "tongue", "😛"),
+    (":p", "tongue", "😛"),
+    (":-p", "tongue", "😛"),
+    (";P", "wink_tongue", "😜"),
+    (";p", "wink_tongue", "😜"),
+    (":(", "frown", "🙁"),
+    (":-(", "frown", "🙁"),
+    ("=(", "frown", "🙁"),
+    (":'(", "cry", "😢"),
+    (":'-(", "cry", "😢"),
+    (">:(", "angry", "😠"),
+    (":O", "surprise", "😮"),
+    (":-O", "surprise", "😮"),
+    (":o", "surprise", "😮"),
+    (":-o", "surprise", "😮"),
+    (":|", "neutral", "😐"),
+    (":-|", "neutral", "😐"),
+    (":/", "confused", "😕"),
+    (":-/", "confused", "😕"),
+    (":\\", "confused", "😕"),
+    (":*", "kiss", "😘"),
+    (":-*", "kiss", "😘"),
+    ("B)", "cool", "😎"),
+    ("B-)", "cool", "😎"),
+    ("8)", "cool", "😎"),
+    ("<3", "heart", "❤️"),
+    ("</3", "broken_heart", "💔"),
+    ("o_O", "flushed", "😳"),
+    ("O_o", "flushed", "😳"),
+    ("o.O", "flushed", "😳"),
+    ("^_^", "happy", "😊"),
+    ("^^", "happy", "😊"),
+    ("-_-", "expressionless", "😑"),
+    ("T_T", "sob", "😭"),
+    (":3", "cat_face", "😺"),
+];
+
+/// Well-known kaomoji as `(kaomoji, name, emoji)`.
+const KAOMOJI: &[(&str, &str, &str)] = &[
+    ("(╯°□°)╯︵ ┻━┻", "table_flip", "😡"),
+    ("(╯°□°）╯︵ ┻━┻", "table_flip", "😡"),
+    ("(ノಠ益ಠ)ノ彡┻━┻", "table_flip", "😡"),
+    ("┬─┬ノ( º _ ºノ)", "table_unflip", "😌"),
+    ("┬─┬ ノ( ゜-゜ノ)", "table_unflip", "😌"),
+    ("¯\\_(ツ)_/¯", "shrug", "🤷"),
+    ("( ͡° ͜ʖ ͡°)", "lenny", "😏"),
+    ("ಠ_ಠ", "disapproval", "😒"),
+    ("ಥ_ಥ", "sob", "😭"),
+    ("(╥﹏╥)", "sob", "😭"),
+    ("(｡◕‿◕｡)", "happy", "😊"),
+    ("(◕‿◕)", "happy", "😊"),
+    ("(^_^)", "happy", "😊"),
+    ("(^▽^)", "happy", "😊"),
+    ("(≧▽≦)", "laugh", "😆"),
+    ("(づ｡◕‿‿◕｡)づ", "hug", "🤗"),
+    ("(っ◔◡◔)っ", "hug", "🤗"),
+    ("ʕ•ᴥ•ʔ", "bear", "🐻"),
+    ("(•_•)", "stare", "😐"),
+    ("(¬_¬)", "side_eye", "😒"),
+    ("(ʘ‿ʘ)", "wide_eyed", "😳"),
+    ("(*^ω^*)", "happy", "😊"),
+    ("(T_T)", "sob", "😭"),
+    ("(>_<)", "frustrated", "😣"),
+    ("(-_-)", "expressionless", "😑"),
+    ("(o_O)", "flushed", "😳"),
+    ("♪~ ᕕ(ᐛ)ᕗ", "dance", "💃"),
+];
+
+/// Characters that make up the eyes and mouths of kaomoji.
+const FACE_PARTS: &str = "°□ಠ◕‿•ᴥ益ω▽´｀ﾟ∀≧≦^⌒ಥ╥﹏ㅂ・｡ˆ˘ツʖ◉⊙♥ᐛ≖◔◡〇";
+
+lazy_static! {
+    static ref KNOWN: AhoCorasick = AhoCorasick::builder()
+        .match_kind(MatchKind::LeftmostLongest)
+        .build(EMOTICONS.iter().chain(KAOMOJI).map(|(text, _, _)| text))
+        .unwrap();
+
+    /// A bracketed face with optional arms, e.g. `ヽ(°〇°)ﾉ`.
+    static ref KAOMOJI_LIKE: Regex = Regex::new(
+        r"[ヽ\\٩ᕕ┐╰ლ〜]?[(（][^()（）\nA-Za-z0-9]{2,15}[)）][ノﾉ╯/۶づっシ┛ᕗ〜]*"
+    ).unwrap();
+}
+
+/// ASCII emoticons only count when they stand apart from words,
+/// so that "http://" or "a:b" are left alone. The ones that read like a
+/// list marker, like "B)" or "8)", also need text before them on their line.
+fn is_standalone(text: &str, range: &Range<usize>) -> bool {
+    let before = text[..range.start].chars().next_back();
+    let after = text[range.end..].chars().next();
+    let emoticon = &text[range.clone()];
+    let is_marker = emoticon.starts_with(|c: char| c.is_ascii_alphanumeric()) && emoticon.ends_with(')');
+    let starts_line = text[..range.start].rsplit('\n').next().is_some_and(|line| line.trim().is_empty());
+    before.is_none_or(char::is_whitespace)
+        && after.is_none_or(|c| c.is_whitespace() || ".,!?;".contains(c))
+        && !(is_marker && starts_line)
+}
+
+/// Whether a bracketed match looks like a face: it has a face part
+/// inside, and no letters that aren't one, so that a parenthetical like
+/// "（東京・大阪）" is left alone.
+fn is_face(kaomoji: &str) -> bool {
+    let inside = kaomoji
+        .find(['(', '（'])
+        .zip(kaomoji.rfind([')', '）']))
+        .map_or("", |(open, close)| &kaomoji[open..close]);
+    inside.chars().any(|c| FACE_PARTS.contains(c))
+        && inside.chars().all(|c| !c.is_alphabetic() || FACE_PARTS.contains(c))
+}
+
+/// Find the emoticons and kaomoji in `text`, in order and without overlaps.
+pub fn find_emoticons(text: &str) -> Vec<(Range<usize>, Emoticon)> {
+    let mut candidates = vec![];
+
+    for m in KNOWN.find_iter(text) {
+        let idx = m.pattern().as_usize();
+        let (kind, (_, name, emoji)) = match idx < EMOTICONS.len() {
+            true => (EmoticonKind::Emoticon, EMOTICONS[idx]),
+            false => (EmoticonKind::Kaomoji, KAOMOJI[idx - EMOTICONS.len()]),
+        };
+        if kind == EmoticonKind::Emoticon && !is_standalone(text, &m.range()) {
+            continue;
+        }
+        candidates.push((m.range(), Emoticon { kind, name, emoji: Some(emoji) }));
+    }
+
+    for m in KAOMOJI_LIKE.find_iter(text) {
+        if is_face(m.as_str()) {
+            let kaomoji = Emoticon { kind: EmoticonKind::Kaomoji, name: "kaomoji", emoji: None };
+            candidates.push((m.range(), kaomoji));
+        }
+    }
+
+    // Prefer the leftmost match, then the longest, then a known one.
+    candidates.sort_by(|a, b| {
+        a.0.start.cmp(&b.0.start)
+        .then(b.0.end.cmp(&a.0.end))
+        .then(b.1.emoji.is_some().cmp(&a.1.emoji.is_some()))
+    });
+    let mut found: Vec<(Range<usize>, Emoticon)> = vec![];
+    for candidate in candidates {
+        if found.last().is_none_or(|(last, _)| candidate.0.start >= last.end) {
+            found.push(candidate);
+        }
+    }
+    found
+}
+
+/// What to do with an emoticon or kaomoji.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmoticonAction {
+    #[default]
+    Remove,
+    /// Replace it by `token`, with `{name}` standing for its name.
+    Token,
+    /// Replace it by the closest emoji. Kaomoji without one are left as they are.
+    Emoji,
+}
+
+/// Options for handling emoticons and kaomoji.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EmoticonOptions {
+    pub action: EmoticonAction,
+    pub token: String,
+    /// Leave kaomoji alone and only handle ASCII emoticons.
+    pub emoticons_only: bool,
+}
+
+impl Default for EmoticonOptions {
+    fn default() -> Self {
+        Self {
+            action: EmoticonAction::default(),
+            token: String::from("<{name}>"),
+            emoticons_only: false,
+        }
+    }
+}
+
+pub fn replace_emoticons(text: &str, options: &EmoticonOptions) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut last = 0;
+    for (range, emoticon) in find_emoticons(text) {
+        if options.emoticons_only && emoticon.kind == EmoticonKind::Kaomoji {
+            continue;
+        }
+        result.push_str(&text[last..range.start]);
+        match options.action {
+            EmoticonAction::Remove => {},
+            EmoticonAction::Token => result.push_str(&options.token.replace("{name}", emoticon.name)),
+            EmoticonAction::Emoji => result.push_str(emoticon.emoji.unwrap_or(&text[range.clone()])),
+        }
+        last = range.end;
+    }
+    result.push_str(&text[last..]);
+    result
+}
+
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    fn with_action(action: EmoticonAction) -> EmoticonOptions {
+        EmoticonOptions { action, ..Default::default() }
+    }
+
+    #[test]
+    pub fn test_find_emoticons() {
+        let found = find_emoticons("hi :-) see http://x.io <3 ;P");
+        let names = found.iter().map(|(_, e)| e.name).collect::<Vec<_>>();
+        assert_eq!(names, vec!["smile", "heart", "wink_tongue"]);
+    }
+
+    #[test]
+    pub fn test_find_kaomoji() {
+        let found = find_emoticons("ugh (╯°□°)╯︵ ┻━┻ and ヽ(°〇°)ﾉ but not (a, b)");
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].1.name, "table_flip");
+        assert_eq!(found[1].1, Emoticon { kind: EmoticonKind::Kaomoji, name: "kaomoji", emoji: None });
+    }
+
+    #[test]
+    pub fn test_replace_emoticons() {
+        let s = "fine :) ¯\\_(ツ)_/¯";
+        assert_eq!(replace_emoticons(s, &with_action(EmoticonAction::Remove)), "fine  ");
+        assert_eq!(replace_emoticons(s, &with_action(EmoticonAction::Token)), "fine <smile> <shrug>");
+        assert_eq!(replace_emoticons(s, &with_action(EmoticonAction::Emoji)), "fine 🙂 🤷");
+    }
+
+    #[test]
+    pub fn test_emoticons_only() {
+        let options = EmoticonOptions { emoticons_only: true, ..Default::default() };
+        assert_eq!(replace_emoticons("ok :D (^_^)", &options), "ok  (^_^)");
+    }
+
+    #[test]
+    pub fn test_list_markers_are_kept() {
+        let options = EmoticonOptions::default();
+        assert_eq!(replace_emoticons("8) item eight\nB) option b", &options), "8) item eight\nB) option b");
+        assert_eq!(replace_emoticons("so cool B)\nXD ok", &options), "so cool \n ok");
+    }
+
+    #[test]
+    pub fn test_cjk_parentheticals_are_kept() {
+        let options = EmoticonOptions::default();
+        assert_eq!(replace_emoticons("会議（東京・大阪）です", &options), "会議（東京・大阪）です");
+        assert_eq!(replace_emoticons("はい (・_・) ね", &options), "はい  ね");
+    }
+}
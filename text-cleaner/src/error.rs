@@ -0,0 +1,92 @@
+use std::fmt;
+
+/// The errors that can occur while building or running a cleaning pipeline.
+#[derive(Debug)]
+pub enum Error {
+    /// A user-supplied pattern is longer than we are willing to compile.
+    PatternTooLong { length: usize, limit: usize },
+    /// A user-supplied pattern failed to compile (or exceeded the size limits).
+    InvalidPattern(regex::Error),
+    /// A user-supplied pattern is empty, so it would match everywhere.
+    EmptyPattern,
+    /// A pipeline spec could not be parsed.
+    InvalidSpec(serde_json::Error),
+    /// A file referenced by a pipeline step could not be read.
+    Io(std::io::Error),
+    /// A dictionary file is malformed.
+    InvalidDictionary { line: usize, reason: String },
+    /// The matcher for a set of patterns could not be built.
+    Automaton(aho_corasick::BuildError),
+    /// A Unicode script name was not recognized.
+    UnknownScript(String),
+    /// A transliteration scheme doesn't apply to the script it was chosen for.
+    UnsupportedScheme { script: String, scheme: String },
+    /// A locale is not a valid BCP 47 language tag.
+    UnknownLocale(String),
+    /// An option that is a share or a similarity is not from 0 to 1.
+    NotAFraction { option: &'static str, value: f64 },
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::PatternTooLong { length, limit } => {
+                write!(f, "pattern is {} bytes long, the limit is {} bytes", length, limit)
+            },
+            Error::InvalidPattern(e) => write!(f, "invalid pattern: {}", e),
+            Error::EmptyPattern => write!(f, "the pattern is empty"),
+            Error::InvalidSpec(e) => write!(f, "invalid pipeline spec: {}", e),
+            Error::Io(e) => write!(f, "{}", e),
+            Error::InvalidDictionary { line, reason } => {
+                write!(f, "invalid dictionary at line {}: {}", line, reason)
+            },
+            Error::Automaton(e) => write!(f, "{}", e),
+            Error::UnknownScript(name) => write!(f, "unknown Unicode script: {}", name),
+            Error::UnsupportedScheme { script, scheme } => {
+                write!(f, "the {} scheme can't be used for {}", scheme, script)
+            },
+            Error::UnknownLocale(locale) => write!(f, "unknown locale: {}", locale),
+            Error::NotAFraction { option, value } => {
+                write!(f, "{} is {}, which is not a fraction from 0 to 1", option, value)
+            },
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::InvalidPattern(e) => Some(e),
+            Error::InvalidSpec(e) => Some(e),
+            Error::Io(e) => Some(e),
+            Error::Automaton(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<regex::Error> for Error {
+    fn from(e: regex::Error) -> Self {
+        Error::InvalidPattern(e)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Error::InvalidSpec(e)
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+impl From<aho_corasick::BuildError> for Error {
+    fn from(e: aho_corasick::BuildError) -> Self {
+        Error::Automaton(e)
+    }
+}
@@ -0,0 +1,160 @@
+use serde::{Deserialize, Serialize};
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+use unicode_script::Script;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::script::{cluster_extension, is_neutral};
+
+/// Options for folding text to ASCII.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FoldingOptions {
+    /// Spell out letters that have no accent-free equivalent instead of
+    /// dropping or truncating them, e.g. "ß" as "ss" and "Æ" as "AE".
+    pub transliterate: bool,
+    /// When removing non-ASCII characters, remove every grapheme cluster
+    /// that isn't entirely ASCII, instead of only its non-ASCII characters.
+    /// This drops `"e\u{301}"` as a whole rather than leaving the `e`, and
+    /// never leaves the ASCII pieces of an emoji sequence (like a keycap's digit) behind.
+    pub whole_clusters: bool,
+}
+
+/// ASCII spellings of the Latin letters and common punctuation
+/// that don't decompose into an ASCII base and combining marks.
+fn fold_char(c: char) -> Option<&'static str> {
+    let folded = match c {
+        'ß' => "ss",
+        'ẞ' => "SS",
+        'Æ' => "AE",
+        'æ' => "ae",
+        'Œ' => "OE",
+        'œ' => "oe",
+        'Ø' => "O",
+        'ø' => "o",
+        'Đ' | 'Ð' => "D",
+        'đ' | 'ð' => "d",
+        'Þ' => "TH",
+        'þ' => "th",
+        'Ł' | 'Ŀ' => "L",
+        'ł' | 'ŀ' => "l",
+        'Ħ' => "H",
+        'ħ' => "h",
+        'Ŋ' => "N",
+        'ŋ' => "n",
+        'Ŧ' => "T",
+        'ŧ' => "t",
+        'ı' => "i",
+        'ĸ' => "q",
+        'ſ' => "s",
+        'ƒ' => "f",
+        '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}' | '\u{2032}' => "'",
+        '\u{201C}' | '\u{201D}' | '\u{201E}' | '\u{201F}' | '\u{2033}' => "\"",
+        '\u{2010}' | '\u{2011}' | '\u{2012}' | '\u{2013}' | '\u{2014}' | '\u{2015}' | '\u{2212}' => "-",
+        '«' => "<<",
+        '»' => ">>",
+        _ => return None,
+    };
+    Some(folded)
+}
+
+/// Spell a grapheme cluster in ASCII, if every part of it can be.
+pub fn transliterate_cluster(cluster: &str) -> Option<String> {
+    let mut result = String::with_capacity(cluster.len());
+    for c in cluster.nfkd() {
+        if is_combining_mark(c) {
+            continue;
+        }
+        match c.is_ascii() {
+            true => result.push(c),
+            false => result.push_str(fold_char(c)?),
+        }
+    }
+    Some(result)
+}
+
+/// Remove the non-ASCII characters of `text`, or with `whole_clusters` the
+/// grapheme clusters that aren't entirely ASCII.
+pub fn remove_non_ascii(text: &str, options: &FoldingOptions) -> String {
+    let mut result = String::with_capacity(text.len());
+    for cluster in text.graphemes(true) {
+        if cluster.is_ascii() {
+            result.push_str(cluster);
+            continue;
+        }
+        if options.transliterate {
+            if let Some(folded) = transliterate_cluster(cluster) {
+                result.push_str(&folded);
+                continue;
+            }
+        }
+        if !options.whole_clusters {
+            result.extend(cluster.chars().filter(char::is_ascii));
+        }
+    }
+    result
+}
+
+/// Strip the accents of the Latin grapheme clusters, whether the accents
+/// are precomposed or trail the letter as combining marks. The marks of
+/// other scripts, like the dakuten of kana or the breve of "й", are part
+/// of their letters and are kept.
+pub fn remove_accents(text: &str, options: &FoldingOptions) -> String {
+    let mut result = String::with_capacity(text.len());
+    for cluster in text.graphemes(true) {
+        if cluster.is_ascii() {
+            result.push_str(cluster);
+            continue;
+        }
+        if options.transliterate {
+            if let Some(folded) = transliterate_cluster(cluster) {
+                result.push_str(&folded);
+                continue;
+            }
+        }
+        let ext = cluster_extension(cluster);
+        if is_neutral(ext) || !ext.contains_script(Script::Latin) {
+            result.push_str(cluster);
+            continue;
+        }
+        let base = cluster.nfd().filter(|c| !is_combining_mark(*c)).collect::<String>();
+        result.push_str(&diacritics::remove_diacritics(&base));
+    }
+    result
+}
+
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    const TRANSLITERATE: FoldingOptions = FoldingOptions { transliterate: true, whole_clusters: false };
+
+    // A keycap, a decomposed "é" and a family emoji joined with ZWJs.
+    const CLUSTERS: &str = "1\u{FE0F}\u{20E3} cafe\u{0301} \u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}!";
+
+    #[test]
+    pub fn test_remove_non_ascii() {
+        assert_eq!(remove_non_ascii(CLUSTERS, &FoldingOptions::default()), "1 cafe !");
+    }
+
+    #[test]
+    pub fn test_remove_non_ascii_drops_whole_clusters() {
+        let options = FoldingOptions { whole_clusters: true, ..Default::default() };
+        assert_eq!(remove_non_ascii(CLUSTERS, &options), " caf !");
+    }
+
+    #[test]
+    pub fn test_remove_non_ascii_transliterate() {
+        assert_eq!(remove_non_ascii("Straße, Æsir, cafe\u{0301} “ok” 😀", &TRANSLITERATE), "Strasse, AEsir, cafe \"ok\" ");
+    }
+
+    #[test]
+    pub fn test_remove_accents() {
+        let options = FoldingOptions::default();
+        assert_eq!(remove_accents("TÅRÖÄÆØ", &options), "TAROAAO");
+        assert_eq!(remove_accents("cafe\u{0301} naïve", &options), "cafe naive");
+        assert_eq!(remove_accents("が 한 й ά パ", &options), "が 한 й ά パ");
+        assert_eq!(remove_accents("TÅRÖÄÆØ ß", &TRANSLITERATE), "TAROAAEO ss");
+    }
+}
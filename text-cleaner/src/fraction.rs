@@ -0,0 +1,64 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// A number from 0 to 1, like a share of pages or a confidence.
+///
+/// Unlike a bare `f64` it is never NaN, so the options that hold one
+/// can still be compared with `Eq`. It is written as a plain number.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default, Serialize, Deserialize)]
+#[serde(try_from = "f64", into = "f64")]
+pub struct Fraction(f64);
+
+impl Fraction {
+    pub const ZERO: Fraction = Fraction(0.0);
+    pub const ONE: Fraction = Fraction(1.0);
+
+    /// `value` as a fraction, if it is from 0 to 1.
+    pub fn new(value: f64) -> Option<Self> {
+        (0.0..=1.0).contains(&value).then_some(Self(value))
+    }
+
+    pub fn get(self) -> f64 {
+        self.0
+    }
+}
+
+impl Eq for Fraction {}
+
+impl TryFrom<f64> for Fraction {
+    type Error = String;
+
+    fn try_from(value: f64) -> Result<Self, Self::Error> {
+        Self::new(value).ok_or_else(|| format!("{} is not a fraction from 0 to 1", value))
+    }
+}
+
+impl From<Fraction> for f64 {
+    fn from(fraction: Fraction) -> Self {
+        fraction.0
+    }
+}
+
+impl fmt::Display for Fraction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_fraction() {
+        assert_eq!(Fraction::new(0.25).map(Fraction::get), Some(0.25));
+        assert_eq!(Fraction::new(1.5), None);
+        assert_eq!(Fraction::new(f64::NAN), None);
+
+        assert_eq!(serde_json::from_str::<Fraction>("0.5").unwrap(), Fraction::new(0.5).unwrap());
+        assert!(serde_json::from_str::<Fraction>("-1").is_err());
+        assert_eq!(serde_json::to_string(&Fraction::ONE).unwrap(), "1.0");
+    }
+}
@@ -0,0 +1,552 @@
+use std::ops::Range;
+
+use html_escape::{decode_html_entities, encode_double_quoted_attribute, encode_single_quoted_attribute, encode_text};
+use serde::{Deserialize, Serialize};
+
+/// Options for converting HTML to plain text.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HtmlOptions {
+    /// Follow the text of a link by its URL in brackets, e.g. `docs [https://docs.rs]`.
+    pub keep_links: bool,
+    /// What list items of unordered lists start with.
+    pub bullet: String,
+}
+
+impl Default for HtmlOptions {
+    fn default() -> Self {
+        Self {
+            keep_links: false,
+            bullet: String::from("- "),
+        }
+    }
+}
+
+/// Elements whose contents are never text.
+const SKIPPED: &[&str] = &["script", "style", "noscript", "template"];
+
+/// Elements that are set apart from the surrounding text by a blank line.
+const PARAGRAPHS: &[&str] = &[
+    "p", "h1", "h2", "h3", "h4", "h5", "h6", "ul", "ol", "dl", "table",
+    "blockquote", "pre", "figure", "hr",
+];
+
+/// Elements that start on a line of their own.
+const BLOCKS: &[&str] = &[
+    "div", "li", "tr", "dt", "dd", "section", "article", "header", "footer",
+    "nav", "aside", "main", "address", "form", "fieldset", "figcaption",
+    "title", "details", "summary", "caption",
+];
+
+/// A start or end tag.
+struct Tag {
+    name: String,
+    closing: bool,
+    /// The whole tag, from `<` to `>`.
+    range: Range<usize>,
+    /// The part of the tag between its name and the closing `>`.
+    attributes: Range<usize>,
+}
+
+/// An attribute of a tag, with the byte range of its (still encoded) value.
+struct Attribute {
+    name: String,
+    value: Option<Range<usize>>,
+    quote: Option<char>,
+}
+
+/// A piece of an HTML document.
+enum Token {
+    Text(Range<usize>),
+    Tag(Tag),
+    /// Comments, doctypes and processing instructions.
+    Markup(Range<usize>),
+    /// The contents of an element like `<script>`, which are not text.
+    Raw(Range<usize>),
+}
+
+/// Parse the tag starting at `start`, which must be a `<`. Returns `None`
+/// if it isn't a tag, in which case the `<` is just text.
+fn parse_tag(html: &str, start: usize) -> Option<Tag> {
+    let rest = &html[start + 1..];
+    let closing = rest.starts_with('/');
+    let rest = rest.strip_prefix('/').unwrap_or(rest);
+    let name_length = rest
+        .find(|c: char| !c.is_ascii_alphanumeric() && c != '-')
+        .unwrap_or(rest.len());
+    if name_length == 0 || !rest.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    let name_end = html.len() - rest.len() + name_length;
+
+    // Find the closing `>`, skipping over quoted attribute values.
+    let mut quote = None;
+    for (i, c) in html[name_end..].char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), _) if q == c => quote = None,
+            (None, '>') => {
+                return Some(Tag {
+                    name: rest[..name_length].to_ascii_lowercase(),
+                    closing,
+                    range: start..name_end + i + 1,
+                    attributes: name_end..name_end + i,
+                });
+            },
+            _ => {},
+        }
+    }
+    None
+}
+
+fn parse_attributes(html: &str, tag: &Tag) -> Vec<Attribute> {
+    let mut attributes = vec![];
+    let mut position = tag.attributes.start;
+    let end = tag.attributes.end;
+    let find = |from: usize, f: &dyn Fn(char) -> bool| html[from..end].find(f).map_or(end, |i| from + i);
+
+    loop {
+        position = find(position, &|c| !c.is_ascii_whitespace() && c != '/');
+        if position == end {
+            return attributes;
+        }
+        let name_end = find(position, &|c| c.is_ascii_whitespace() || c == '=' || c == '/');
+        let mut attribute = Attribute {
+            name: html[position..name_end].to_ascii_lowercase(),
+            value: None,
+            quote: None,
+        };
+        position = find(name_end, &|c| !c.is_ascii_whitespace());
+        if html[position..end].starts_with('=') {
+            let value_start = find(position + 1, &|c| !c.is_ascii_whitespace());
+            let (value, next) = match html[value_start..end].chars().next() {
+                Some(q @ ('"' | '\'')) => {
+                    attribute.quote = Some(q);
+                    let value_end = find(value_start + 1, &|c| c == q);
+                    (value_start + 1..value_end, (value_end + 1).min(end))
+                },
+                _ => {
+                    let value_end = find(value_start, &|c| c.is_ascii_whitespace());
+                    (value_start..value_end, value_end)
+                },
+            };
+            attribute.value = Some(value);
+            position = next;
+        } else if name_end == position {
+            // A stray `/` or `=`; skip it.
+            position += 1;
+        }
+        attributes.push(attribute);
+    }
+}
+
+/// The value of an attribute, with its entities decoded.
+fn attribute(html: &str, tag: &Tag, name: &str) -> Option<String> {
+    parse_attributes(html, tag)
+        .into_iter()
+        .find(|attribute| attribute.name == name)
+        .and_then(|attribute| attribute.value)
+        .map(|value| decode_html_entities(&html[value]).into_owned())
+}
+
+/// Splits an HTML document into [`Token`]s. Anything that isn't markup is
+/// text, so a stray `<` as in `1 < 2` is kept as part of the text around it.
+struct Tokens<'a> {
+    html: &'a str,
+    position: usize,
+    /// The element whose raw contents come next.
+    raw: Option<String>,
+}
+
+fn tokens(html: &str) -> Tokens<'_> {
+    Tokens { html, position: 0, raw: None }
+}
+
+impl Tokens<'_> {
+    fn markup_end(&self, start: usize) -> Option<usize> {
+        let rest = &self.html[start..];
+        if rest.starts_with("<!--") {
+            Some(rest.find("-->").map_or(self.html.len(), |i| start + i + 3))
+        } else if rest.starts_with("<!") || rest.starts_with("<?") {
+            Some(rest.find('>').map_or(self.html.len(), |i| start + i + 1))
+        } else {
+            None
+        }
+    }
+
+    fn is_markup(&self, start: usize) -> bool {
+        self.markup_end(start).is_some() || parse_tag(self.html, start).is_some()
+    }
+}
+
+/// Where the first `</name` (in any case) at or after `start` begins.
+fn find_closing_tag(html: &str, start: usize, name: &str) -> Option<usize> {
+    let bytes = html.as_bytes();
+    let mut position = start;
+    while let Some(i) = html[position..].find("</") {
+        let tag = position + i;
+        let name_end = tag + 2 + name.len();
+        if name_end <= bytes.len() && bytes[tag + 2..name_end].eq_ignore_ascii_case(name.as_bytes()) {
+            return Some(tag);
+        }
+        position = tag + 2;
+    }
+    None
+}
+
+impl Iterator for Tokens<'_> {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Token> {
+        let html = self.html;
+        let start = self.position;
+        if start >= html.len() {
+            return None;
+        }
+
+        if let Some(name) = self.raw.take() {
+            let end = find_closing_tag(html, start, &name).unwrap_or(html.len());
+            if end > start {
+                self.position = end;
+                return Some(Token::Raw(start..end));
+            }
+        }
+
+        if let Some(end) = self.markup_end(start) {
+            self.position = end;
+            return Some(Token::Markup(start..end));
+        }
+        if let Some(tag) = html[start..].starts_with('<').then(|| parse_tag(html, start)).flatten() {
+            if SKIPPED.contains(&tag.name.as_str()) && !tag.closing {
+                self.raw = Some(tag.name.clone());
+            }
+            self.position = tag.range.end;
+            return Some(Token::Tag(tag));
+        }
+
+        // Skip the first character, which may be a stray `<`.
+        let mut end = start + html[start..].chars().next().map_or(1, char::len_utf8);
+        while let Some(i) = html[end..].find('<') {
+            end += i;
+            if self.is_markup(end) {
+                break;
+            }
+            end += 1;
+        }
+        let end = match end < html.len() && self.is_markup(end) {
+            true => end,
+            false => html.len(),
+        };
+        self.position = end;
+        Some(Token::Text(start..end))
+    }
+}
+
+/// Builds the text, collapsing whitespace and line breaks the way a browser would.
+#[derive(Default)]
+pub(crate) struct Writer {
+    pub(crate) text: String,
+    /// Newlines to write before the next piece of text.
+    pub(crate) pending_newlines: usize,
+    pub(crate) pending_space: bool,
+}
+
+impl Writer {
+    pub(crate) fn break_line(&mut self, newlines: usize) {
+        self.pending_newlines = self.pending_newlines.max(newlines);
+        self.pending_space = false;
+    }
+
+    pub(crate) fn flush(&mut self) {
+        if !self.text.is_empty() {
+            if self.pending_newlines > 0 {
+                self.text.push_str(&"\n".repeat(self.pending_newlines));
+            } else if self.pending_space {
+                self.text.push(' ');
+            }
+        }
+        self.pending_newlines = 0;
+        self.pending_space = false;
+    }
+
+    /// Write text as is, e.g. a bullet or the contents of a `<pre>`.
+    pub(crate) fn write_raw(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        self.flush();
+        self.text.push_str(text);
+    }
+
+    pub(crate) fn write(&mut self, text: &str) {
+        for (i, word) in text.split_ascii_whitespace().enumerate() {
+            if i > 0 || text.starts_with(|c: char| c.is_ascii_whitespace()) {
+                self.pending_space = true;
+            }
+            self.write_raw(word);
+        }
+        if text.ends_with(|c: char| c.is_ascii_whitespace()) {
+            self.pending_space = true;
+        }
+    }
+}
+
+/// Convert HTML into plain text.
+///
+/// Tags are stripped and the contents of `<script>` and `<style>` dropped.
+/// Entities are decoded, with non-breaking spaces becoming plain spaces.
+/// Block elements start new lines and list items become bullet (or numbered) lines.
+pub fn html_to_text(html: &str, options: &HtmlOptions) -> String {
+    let mut writer = Writer::default();
+    // One entry per open list: `None` for unordered ones, or the next number.
+    let mut lists: Vec<Option<usize>> = vec![];
+    let mut links: Vec<Option<String>> = vec![];
+    let mut preformatted = 0usize;
+
+    for token in tokens(html) {
+        let tag = match token {
+            Token::Text(range) => {
+                let text = decode_html_entities(&html[range]).replace('\u{A0}', " ");
+                match preformatted > 0 {
+                    true => writer.write_raw(&text),
+                    false => writer.write(&text),
+                }
+                continue;
+            },
+            Token::Markup(_) | Token::Raw(_) => continue,
+            Token::Tag(tag) => tag,
+        };
+        let name = tag.name.as_str();
+
+        match (name, tag.closing) {
+            ("br", _) => {
+                writer.flush();
+                if !writer.text.is_empty() {
+                    writer.text.push('\n');
+                }
+            },
+            ("ul", false) => lists.push(None),
+            ("ol", false) => lists.push(Some(1)),
+            ("ul" | "ol", true) => {
+                lists.pop();
+            },
+            ("li", false) => {
+                writer.break_line(1);
+                let depth = lists.len().saturating_sub(1);
+                let marker = match lists.last_mut() {
+                    Some(Some(number)) => {
+                        *number += 1;
+                        format!("{}. ", *number - 1)
+                    },
+                    _ => options.bullet.clone(),
+                };
+                writer.write_raw(&format!("{}{}", "  ".repeat(depth), marker));
+            },
+            ("td" | "th", false) => writer.pending_space = true,
+            ("pre", false) => preformatted += 1,
+            ("pre", true) => preformatted = preformatted.saturating_sub(1),
+            ("a", false) => links.push(attribute(html, &tag, "href")),
+            ("a", true) => {
+                if let Some(Some(href)) = links.pop() {
+                    let internal = href.starts_with('#') || href.starts_with("javascript:");
+                    if options.keep_links && !href.is_empty() && !internal {
+                        writer.pending_space = true;
+                        writer.write_raw(&format!("[{}]", href));
+                    }
+                }
+            },
+            _ => {},
+        }
+        let nested_list = matches!(name, "ul" | "ol") && !lists.is_empty() && (tag.closing || lists.len() > 1);
+        if nested_list {
+            writer.break_line(1);
+        } else if PARAGRAPHS.contains(&name) {
+            writer.break_line(2);
+        } else if BLOCKS.contains(&name) && name != "li" {
+            writer.break_line(1);
+        }
+    }
+    writer.text
+}
+
+
+
+/// Options for cleaning the text of an HTML document in place.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HtmlTextOptions {
+    /// Attributes whose values are cleaned too, e.g. `"alt"` and `"title"`.
+    pub attributes: Vec<String>,
+}
+
+/// Elements whose text is left exactly as it is.
+const PRESERVED: &[&str] = &["pre", "code", "textarea"];
+
+/// Whether a token is the tag of an inline element, like `<b>` or `</a>`,
+/// which doesn't separate the words on either side of it.
+fn is_inline(token: Option<&Token>) -> bool {
+    match token {
+        Some(Token::Tag(tag)) => {
+            let name = tag.name.as_str();
+            name != "br" && !PARAGRAPHS.contains(&name) && !BLOCKS.contains(&name)
+        },
+        _ => false,
+    }
+}
+
+/// Run `clean` over every text node of an HTML document and serialize it back.
+///
+/// Tags, comments and the contents of `<script>`, `<style>`, `<pre>`,
+/// `<code>` and `<textarea>` are left untouched, as are text nodes that are only whitespace.
+/// `clean` sees text with its entities decoded, and what it returns is
+/// escaped again. Text nodes that it leaves unchanged keep their original encoding.
+pub fn clean_text_nodes<F>(html: &str, options: &HtmlTextOptions, clean: F) -> String
+where
+    F: Fn(&str) -> String
+{
+    let mut result = String::with_capacity(html.len());
+    let mut preserved = 0usize;
+    let tokens = tokens(html).collect::<Vec<_>>();
+
+    for (i, token) in tokens.iter().enumerate() {
+        match token {
+            Token::Text(range) => {
+                let text = &html[range.clone()];
+                if preserved > 0 || text.trim().is_empty() {
+                    result.push_str(text);
+                    continue;
+                }
+                let decoded = decode_html_entities(text);
+                let cleaned = clean(&decoded);
+                if cleaned == decoded {
+                    result.push_str(text);
+                    continue;
+                }
+                // Trimming a text node must not glue its words to those of an inline element next to it.
+                let previous = i.checked_sub(1).and_then(|i| tokens.get(i));
+                let space_before = decoded.starts_with(char::is_whitespace)
+                    && !cleaned.starts_with(char::is_whitespace)
+                    && is_inline(previous);
+                let space_after = decoded.ends_with(char::is_whitespace)
+                    && !cleaned.ends_with(char::is_whitespace)
+                    && is_inline(tokens.get(i + 1));
+                if cleaned.is_empty() {
+                    if space_before || space_after {
+                        result.push(' ');
+                    }
+                    continue;
+                }
+                if space_before {
+                    result.push(' ');
+                }
+                result.push_str(&encode_text(&cleaned));
+                if space_after {
+                    result.push(' ');
+                }
+            },
+            Token::Markup(range) | Token::Raw(range) => result.push_str(&html[range.clone()]),
+            Token::Tag(tag) => {
+                if PRESERVED.contains(&tag.name.as_str()) {
+                    preserved = match tag.closing {
+                        true => preserved.saturating_sub(1),
+                        false => preserved + 1,
+                    };
+                }
+                let mut last = tag.range.start;
+                for attribute in parse_attributes(html, tag) {
+                    let value = match attribute.value {
+                        Some(value) if options.attributes.iter().any(|name| name.eq_ignore_ascii_case(&attribute.name)) => value,
+                        _ => continue,
+                    };
+                    let decoded = decode_html_entities(&html[value.clone()]);
+                    let cleaned = clean(&decoded);
+                    if cleaned == decoded {
+                        continue;
+                    }
+                    let encoded = match attribute.quote {
+                        Some('\'') => encode_single_quoted_attribute(&cleaned).into_owned(),
+                        Some(_) => encode_double_quoted_attribute(&cleaned).into_owned(),
+                        None => format!("\"{}\"", encode_double_quoted_attribute(&cleaned)),
+                    };
+                    result.push_str(&html[last..value.start]);
+                    result.push_str(&encoded);
+                    last = value.end;
+                }
+                result.push_str(&html[last..tag.range.end]);
+            },
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    fn convert(html: &str) -> String {
+        html_to_text(html, &HtmlOptions::default())
+    }
+
+    #[test]
+    pub fn test_strip_tags() {
+        assert_eq!(convert("<p>Hello <b>big</b>\n   <i>world</i>!</p>"), "Hello big world!");
+        assert_eq!(convert("<div class=\"a>b\">x</div><!-- <p>hidden</p> -->y"), "x\ny");
+        assert_eq!(convert("1 < 2 and <3"), "1 < 2 and <3");
+    }
+
+    #[test]
+    pub fn test_skip_scripts_and_styles() {
+        let html = "<head><style>p { color: red; }</style></head><p>Text</p><SCRIPT>alert('</p>')</script >";
+        assert_eq!(convert(html), "Text");
+        assert_eq!(convert("<style>é</b></ STYLE></StYlE>ok<script>"), "ok");
+    }
+
+    #[test]
+    pub fn test_decode_entities() {
+        assert_eq!(convert("Fish &amp; chips&nbsp;&mdash; &#163;5 &#x263A; &hellip;"), "Fish & chips — £5 ☺ …");
+    }
+
+    #[test]
+    pub fn test_blocks_and_lists() {
+        let html = "<h1>Title</h1><p>One<br>Two</p><ul><li>a</li><li>b<ol><li>c</li><li>d</li></ol></li></ul><div>End</div>";
+        assert_eq!(convert(html), "Title\n\nOne\nTwo\n\n- a\n- b\n  1. c\n  2. d\n\nEnd");
+    }
+
+    #[test]
+    pub fn test_preformatted() {
+        assert_eq!(convert("<p>code:</p><pre>fn main() {\n    x();\n}</pre>"), "code:\n\nfn main() {\n    x();\n}");
+    }
+
+    #[test]
+    pub fn test_keep_links() {
+        let html = r##"See <a href="https://docs.rs/?a=1&amp;b=2">the docs</a> or <a href="#top">top</a>."##;
+        assert_eq!(convert(html), "See the docs or top.");
+
+        let options = HtmlOptions { keep_links: true, ..Default::default() };
+        assert_eq!(html_to_text(html, &options), "See the docs [https://docs.rs/?a=1&b=2] or top.");
+    }
+
+    #[test]
+    pub fn test_clean_text_nodes() {
+        let html = "<p class=\"x  y\">  Hello &amp;   <b>big</b>   world </p>\n<pre>keep   this</pre>";
+        let cleaned = clean_text_nodes(html, &HtmlTextOptions::default(), |s| s.split_whitespace().collect::<Vec<_>>().join(" "));
+        assert_eq!(cleaned, "<p class=\"x  y\">Hello &amp; <b>big</b> world</p>\n<pre>keep   this</pre>");
+        let cleaned = clean_text_nodes("<b>a</b> foo <i>b</i>", &HtmlTextOptions::default(), |s| s.replace("foo", "").trim().to_owned());
+        assert_eq!(cleaned, "<b>a</b> <i>b</i>");
+    }
+
+    #[test]
+    pub fn test_clean_text_nodes_escapes_output() {
+        let html = "<div>a &lt;b&gt; &nbsp;c<!-- a --><script>if (a < b) {}</script></div>";
+        let cleaned = clean_text_nodes(html, &HtmlTextOptions::default(), |s| s.replace('a', "<&>"));
+        assert_eq!(cleaned, "<div>&lt;&amp;&gt; &lt;b&gt; \u{A0}c<!-- a --><script>if (a < b) {}</script></div>");
+    }
+
+    #[test]
+    pub fn test_clean_attributes() {
+        let options = HtmlTextOptions { attributes: vec![String::from("alt"), String::from("title")] };
+        let html = "<img src=cafe.png ALT='Café' title=Café><a href=\"café\" title=\"ok\">x</a>";
+        let cleaned = clean_text_nodes(html, &options, |s| s.replace('é', "e \""));
+        assert_eq!(cleaned, "<img src=cafe.png ALT='Cafe \"' title=\"Cafe &quot;\"><a href=\"café\" title=\"ok\">x</a>");
+    }
+}
@@ -0,0 +1,208 @@
+use serde::{Deserialize, Serialize};
+use whatlang::{Detector, Lang};
+
+use crate::scope::paragraphs;
+
+/// The languages that language-specific steps know about, by ISO 639-1 code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    #[serde(rename = "en")]
+    English,
+    #[serde(rename = "de")]
+    German,
+    #[serde(rename = "fr")]
+    French,
+    #[serde(rename = "es")]
+    Spanish,
+    #[serde(rename = "it")]
+    Italian,
+    #[serde(rename = "pt")]
+    Portuguese,
+    #[serde(rename = "nl")]
+    Dutch,
+    #[serde(rename = "ru")]
+    Russian,
+}
+
+impl Language {
+    pub const ALL: [Language; 8] = [
+        Language::English,
+        Language::German,
+        Language::French,
+        Language::Spanish,
+        Language::Italian,
+        Language::Portuguese,
+        Language::Dutch,
+        Language::Russian,
+    ];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::German => "de",
+            Language::French => "fr",
+            Language::Spanish => "es",
+            Language::Italian => "it",
+            Language::Portuguese => "pt",
+            Language::Dutch => "nl",
+            Language::Russian => "ru",
+        }
+    }
+
+    /// The English name of the language.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::German => "German",
+            Language::French => "French",
+            Language::Spanish => "Spanish",
+            Language::Italian => "Italian",
+            Language::Portuguese => "Portuguese",
+            Language::Dutch => "Dutch",
+            Language::Russian => "Russian",
+        }
+    }
+
+    fn lang(&self) -> Lang {
+        match self {
+            Language::English => Lang::Eng,
+            Language::German => Lang::Deu,
+            Language::French => Lang::Fra,
+            Language::Spanish => Lang::Spa,
+            Language::Italian => Lang::Ita,
+            Language::Portuguese => Lang::Por,
+            Language::Dutch => Lang::Nld,
+            Language::Russian => Lang::Rus,
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Language> {
+        Language::ALL.into_iter().find(|language| language.code().eq_ignore_ascii_case(code))
+    }
+}
+
+/// The language a text was detected to be in, and how confident the
+/// detector is of it, from 0 to 1.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Detection {
+    pub language: Language,
+    pub confidence: f64,
+}
+
+/// The detected language of the paragraph at `start..end` (in bytes).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParagraphLanguage {
+    pub start: usize,
+    pub end: usize,
+    pub detection: Option<Detection>,
+}
+
+/// Identifies the language of a text among [`Language::ALL`] by comparing
+/// its trigrams with bundled profiles. Works offline.
+#[derive(Debug, Clone)]
+pub struct LanguageDetector {
+    detector: Detector,
+}
+
+impl Default for LanguageDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LanguageDetector {
+    pub fn new() -> Self {
+        let languages = Language::ALL.iter().map(|language| language.lang()).collect();
+        Self { detector: Detector::with_allowlist(languages) }
+    }
+
+    /// The language of `text`, or `None` if it has no letters to go by.
+    pub fn detect(&self, text: &str) -> Option<Detection> {
+        let info = self.detector.detect(text)?;
+        let language = Language::ALL.into_iter().find(|language| language.lang() == info.lang())?;
+        Some(Detection { language, confidence: info.confidence() })
+    }
+
+    /// The language of each paragraph (separated by blank lines) of `text`.
+    pub fn detect_paragraphs(&self, text: &str) -> Vec<ParagraphLanguage> {
+        paragraphs(text)
+            .into_iter()
+            .filter(|paragraph| !text[paragraph.clone()].trim().is_empty())
+            .map(|paragraph| ParagraphLanguage {
+                detection: self.detect(&text[paragraph.clone()]),
+                start: paragraph.start,
+                end: paragraph.end,
+            })
+            .collect()
+    }
+}
+
+pub fn detect_language(text: &str) -> Option<Detection> {
+    LanguageDetector::new().detect(text)
+}
+
+/// Run `f` over the whole of `text` or, with `per_paragraph`, over each of
+/// its paragraphs. The paragraphs `f` returns `None` for are dropped, along
+/// with the blank lines after them.
+pub(crate) fn map_paragraphs<F>(text: &str, per_paragraph: bool, f: F) -> String
+where
+    F: Fn(&str) -> Option<String>
+{
+    if !per_paragraph {
+        return f(text).unwrap_or_default();
+    }
+    let paragraphs = paragraphs(text);
+    let mut result = String::with_capacity(text.len());
+    let mut separator = &text[..paragraphs[0].start];
+    for (i, paragraph) in paragraphs.iter().enumerate() {
+        let next = paragraphs.get(i + 1).map_or(text.len(), |next| next.start);
+        if let Some(mapped) = f(&text[paragraph.clone()]) {
+            result.push_str(separator);
+            result.push_str(&mapped);
+            separator = &text[paragraph.end..next];
+        }
+    }
+    if !result.is_empty() {
+        result.push_str(&text[paragraphs.last().unwrap().end..]);
+    }
+    result
+}
+
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    const ENGLISH: &str = "The weather was lovely today, so we walked along the river and talked about our plans.";
+    const GERMAN: &str = "Das Wetter war heute sehr schön, deshalb sind wir am Fluss spazieren gegangen.";
+    const RUSSIAN: &str = "Сегодня была прекрасная погода, поэтому мы гуляли вдоль реки.";
+
+    #[test]
+    pub fn test_detect() {
+        let detection = detect_language(ENGLISH).unwrap();
+        assert_eq!(detection.language, Language::English);
+        assert!(detection.confidence > 0.5);
+        assert_eq!(detect_language(GERMAN).unwrap().language, Language::German);
+        assert_eq!(detect_language(RUSSIAN).unwrap().language, Language::Russian);
+        assert_eq!(detect_language("1234 !!"), None);
+    }
+
+    #[test]
+    pub fn test_detect_paragraphs() {
+        let text = format!("{}\n\n{}\n", ENGLISH, GERMAN);
+        let paragraphs = LanguageDetector::new().detect_paragraphs(&text);
+        assert_eq!(paragraphs.len(), 2);
+        assert_eq!(paragraphs[1].detection.unwrap().language, Language::German);
+        assert_eq!(text[paragraphs[1].start..paragraphs[1].end].trim_end(), GERMAN);
+    }
+
+    #[test]
+    pub fn test_map_paragraphs() {
+        let text = "\na\n\nb\n\n\nc\n";
+        assert_eq!(map_paragraphs(text, true, |p| (p != "b").then(|| p.to_uppercase())), "\nA\n\nC\n");
+        assert_eq!(map_paragraphs(text, true, |p| (p == "b").then(|| p.to_owned())), "b");
+        assert_eq!(map_paragraphs(text, true, |_| None), "");
+        assert_eq!(map_paragraphs(text, false, |p| Some(p.trim().to_owned())), "a\n\nb\n\n\nc");
+    }
+}
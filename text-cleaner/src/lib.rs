@@ -1 +1,27 @@
+pub mod alphanumeric;
+pub mod boilerplate;
+pub mod case;
 pub mod clean;
+pub mod dictionary;
+pub mod emoji;
+pub mod emoticon;
+pub mod error;
+pub mod folding;
+pub mod fraction;
+pub mod html;
+pub mod language;
+pub mod lines;
+pub mod markdown;
+pub mod near_duplicates;
+pub mod pipeline;
+pub mod protect;
+pub mod punctuation;
+pub mod repair;
+pub mod replace;
+pub mod scope;
+pub mod script;
+pub mod segment;
+pub mod stemming;
+pub mod stopwords;
+pub mod transliterate;
+pub mod wrap;
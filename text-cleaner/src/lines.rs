@@ -0,0 +1,488 @@
+use std::cmp::Ordering;
+use std::collections::{HashSet, VecDeque};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::io::{BufRead, Write};
+
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use icu_collator::{Collator, CollatorOptions, Strength};
+use icu_provider::DataLocale;
+
+use crate::error::{Error, Result};
+use crate::replace::{CompiledRule, ReplaceRule};
+
+/// Split `text` into lines, along with whether it ends with a newline.
+fn split_lines(text: &str) -> (Vec<&str>, bool) {
+    let trailing = text.ends_with('\n');
+    let text = text.strip_suffix('\n').unwrap_or(text);
+    match text.is_empty() && !trailing {
+        true => (vec![], false),
+        false => (text.split('\n').collect(), trailing),
+    }
+}
+
+fn join_lines<S: AsRef<str>>(lines: &[S], trailing: bool) -> String {
+    let mut text = lines.iter().map(AsRef::as_ref).collect::<Vec<_>>().join("\n");
+    if trailing && !lines.is_empty() {
+        text.push('\n');
+    }
+    text
+}
+
+/// Options for removing duplicate lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DedupeOptions {
+    /// Compare lines regardless of case.
+    pub ignore_case: bool,
+    /// Compare lines regardless of leading, trailing and repeated whitespace.
+    pub ignore_whitespace: bool,
+    /// Remove repeated blank lines too. By default they are all kept,
+    /// since they separate paragraphs.
+    pub blank_lines: bool,
+    /// Remember at most this many distinct lines, forgetting the oldest ones
+    /// first, so that memory stays bounded on large inputs. A line is only
+    /// recognized as a duplicate if it was seen while still remembered.
+    pub memory: Option<usize>,
+    /// When streaming with `memory` set, remember lines by a 64-bit hash of
+    /// their normalized form rather than by the form itself. This uses less
+    /// memory, but a hash collision drops a line that isn't a duplicate.
+    pub hash_lines: bool,
+}
+
+/// How a line is remembered: by its normalized form or a hash of it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum LineKey {
+    Line(String),
+    Hash(u64),
+}
+
+/// Removes duplicate lines, one line at a time.
+#[derive(Debug, Clone, Default)]
+pub struct LineDeduplicator {
+    options: DedupeOptions,
+    seen: HashSet<LineKey>,
+    order: VecDeque<LineKey>,
+}
+
+impl LineDeduplicator {
+    pub fn new(options: &DedupeOptions) -> Self {
+        Self { options: *options, ..Default::default() }
+    }
+
+    /// The form lines are compared in.
+    fn normalize(&self, line: &str) -> String {
+        let line = match self.options.ignore_whitespace {
+            true => line.split_whitespace().collect::<Vec<_>>().join(" "),
+            false => line.to_owned(),
+        };
+        match self.options.ignore_case {
+            true => line.to_lowercase(),
+            false => line,
+        }
+    }
+
+    /// Whether `line` (without its line break) should be kept, i.e. it
+    /// wasn't seen before.
+    pub fn keep(&mut self, line: &str) -> bool {
+        self.keep_as(line, false)
+    }
+
+    fn keep_as(&mut self, line: &str, hashed: bool) -> bool {
+        if line.trim().is_empty() && !self.options.blank_lines {
+            return true;
+        }
+        let normalized = self.normalize(line);
+        let key = match hashed {
+            true => {
+                let mut hasher = DefaultHasher::new();
+                normalized.hash(&mut hasher);
+                LineKey::Hash(hasher.finish())
+            },
+            false => LineKey::Line(normalized),
+        };
+        if !self.seen.insert(key.clone()) {
+            return false;
+        }
+        if let Some(memory) = self.options.memory {
+            self.order.push_back(key);
+            while self.order.len() > memory {
+                if let Some(oldest) = self.order.pop_front() {
+                    self.seen.remove(&oldest);
+                }
+            }
+        }
+        true
+    }
+
+    pub fn apply(&mut self, text: &str) -> String {
+        let (lines, trailing) = split_lines(text);
+        let kept = lines.into_iter().filter(|line| self.keep(line)).collect::<Vec<_>>();
+        join_lines(&kept, trailing)
+    }
+
+    /// Copy the lines of `reader` that weren't seen before to `writer`,
+    /// without reading the whole input at once. Returns how many lines were removed.
+    pub fn stream<R: BufRead, W: Write>(&mut self, mut reader: R, mut writer: W) -> Result<usize> {
+        let hashed = self.options.hash_lines && self.options.memory.is_some();
+        let mut line = String::new();
+        let mut removed = 0;
+        loop {
+            line.clear();
+            if reader.read_line(&mut line)? == 0 {
+                break;
+            }
+            let content = line.trim_end_matches('\n').trim_end_matches('\r');
+            match self.keep_as(content, hashed) {
+                true => writer.write_all(line.as_bytes())?,
+                false => removed += 1,
+            }
+        }
+        writer.flush()?;
+        Ok(removed)
+    }
+}
+
+pub fn dedupe_lines(text: &str, options: &DedupeOptions) -> String {
+    LineDeduplicator::new(options).apply(text)
+}
+
+/// Whether the lines a [`LineFilter`] matches are the ones to keep or the ones to remove.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterMode {
+    #[default]
+    Keep,
+    Remove,
+}
+
+/// Keep or remove the lines that contain a match of a pattern.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct LineFilter {
+    pub pattern: String,
+    #[serde(default)]
+    pub mode: FilterMode,
+    #[serde(default)]
+    pub case_insensitive: bool,
+}
+
+/// A [`LineFilter`] that is ready to be applied.
+#[derive(Debug, Clone)]
+pub struct CompiledLineFilter {
+    rule: CompiledRule,
+    mode: FilterMode,
+}
+
+impl LineFilter {
+    pub fn compile(&self) -> Result<CompiledLineFilter> {
+        Ok(CompiledLineFilter {
+            rule: ReplaceRule::regex(&self.pattern, "").case_insensitive(self.case_insensitive).compile()?,
+            mode: self.mode,
+        })
+    }
+}
+
+impl CompiledLineFilter {
+    pub fn apply(&self, text: &str) -> String {
+        let (lines, trailing) = split_lines(text);
+        let kept = lines
+            .into_iter()
+            .filter(|line| self.rule.matches(line).is_empty() == (self.mode == FilterMode::Remove))
+            .collect::<Vec<_>>();
+        join_lines(&kept, trailing)
+    }
+}
+
+/// Bounds on the length of lines, in characters. Lines outside of them are removed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LineLengthOptions {
+    pub min: Option<usize>,
+    pub max: Option<usize>,
+}
+
+pub fn limit_line_length(text: &str, options: &LineLengthOptions) -> String {
+    let (lines, trailing) = split_lines(text);
+    let kept = lines
+        .into_iter()
+        .filter(|line| {
+            let length = line.chars().count();
+            options.min.is_none_or(|min| length >= min) && options.max.is_none_or(|max| length <= max)
+        })
+        .collect::<Vec<_>>();
+    join_lines(&kept, trailing)
+}
+
+/// How lines are compared when sorting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    /// By code point.
+    #[default]
+    Lexical,
+    /// Like `Lexical`, except runs of digits are compared by their value,
+    /// so that "file2" comes before "file10".
+    Natural,
+    /// By the Unicode collation rules of the `locale`, or the rules shared
+    /// by most languages without one. Case and accents only break ties, so
+    /// "émile" sits between "Emile" and "Eric", and with `"sv"` (Swedish)
+    /// "ö" comes after "z".
+    Collation,
+}
+
+/// Options for sorting lines.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SortOptions {
+    pub order: SortOrder,
+    pub reverse: bool,
+    pub ignore_case: bool,
+    /// The BCP 47 language tag whose collation rules are used, e.g. `"de"` or `"sv"`.
+    pub locale: Option<String>,
+}
+
+/// Compare two strings, with runs of digits compared by their value.
+fn natural_cmp(a: &str, b: &str) -> Ordering {
+    lazy_static! {
+        static ref CHUNK: Regex = Regex::new(r"\d+|\D+").unwrap();
+    }
+    let mut a_chunks = CHUNK.find_iter(a).map(|m| m.as_str());
+    let mut b_chunks = CHUNK.find_iter(b).map(|m| m.as_str());
+    loop {
+        let (x, y) = match (a_chunks.next(), b_chunks.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) => (x, y),
+        };
+        let numeric = x.starts_with(|c: char| c.is_ascii_digit()) && y.starts_with(|c: char| c.is_ascii_digit());
+        let ordering = match numeric {
+            true => {
+                let (x, y) = (x.trim_start_matches('0'), y.trim_start_matches('0'));
+                x.len().cmp(&y.len()).then_with(|| x.cmp(y))
+            },
+            false => x.cmp(y),
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+}
+
+/// Sorts lines according to a set of [`SortOptions`].
+pub struct LineSorter {
+    options: SortOptions,
+    collator: Option<Collator>,
+}
+
+impl LineSorter {
+    pub fn new(options: &SortOptions) -> Result<Self> {
+        let collator = match options.order {
+            SortOrder::Collation => {
+                let locale = options.locale.as_deref().unwrap_or("und");
+                let unknown = || Error::UnknownLocale(locale.to_owned());
+                let mut collator_options = CollatorOptions::new();
+                if options.ignore_case {
+                    collator_options.strength = Some(Strength::Secondary);
+                }
+                let data_locale = locale.parse::<DataLocale>().map_err(|_| unknown())?;
+                Some(Collator::try_new(&data_locale, collator_options).map_err(|_| unknown())?)
+            },
+            _ => None,
+        };
+        Ok(Self { options: options.clone(), collator })
+    }
+
+    pub fn apply(&self, text: &str) -> String {
+        let (mut lines, trailing) = split_lines(text);
+        let fold = |line: &str| match self.options.ignore_case {
+            true => line.to_lowercase(),
+            false => line.to_owned(),
+        };
+        match &self.collator {
+            Some(collator) => lines.sort_by(|a, b| collator.compare(a, b)),
+            None if self.options.order == SortOrder::Natural => lines.sort_by(|a, b| natural_cmp(&fold(a), &fold(b))),
+            None => lines.sort_by_cached_key(|line| fold(line)),
+        }
+        if self.options.reverse {
+            lines.reverse();
+        }
+        join_lines(&lines, trailing)
+    }
+}
+
+pub fn sort_lines(text: &str, options: &SortOptions) -> Result<String> {
+    Ok(LineSorter::new(options)?.apply(text))
+}
+
+/// Options for numbering lines.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NumberingOptions {
+    /// The number of the first line.
+    pub start: usize,
+    /// What goes between a number and its line.
+    pub separator: String,
+    /// Right-align the numbers.
+    pub align: bool,
+}
+
+impl Default for NumberingOptions {
+    fn default() -> Self {
+        Self {
+            start: 1,
+            separator: String::from(". "),
+            align: false,
+        }
+    }
+}
+
+pub fn number_lines(text: &str, options: &NumberingOptions) -> String {
+    let (lines, trailing) = split_lines(text);
+    let width = match options.align {
+        true => (options.start + lines.len().saturating_sub(1)).to_string().len(),
+        false => 0,
+    };
+    let numbered = lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| format!("{:>width$}{}{}", options.start + i, options.separator, line, width = width))
+        .collect::<Vec<_>>();
+    join_lines(&numbered, trailing)
+}
+
+/// The lines of `lines` without their leading numbers, if every line that
+/// isn't blank starts with one and they count up by one, as in `"1. a\n2. b"`.
+/// At least two lines must be numbered, so that a single line starting with
+/// a number (like a year) is left as it is.
+fn strip_counting_numbers<'a>(lines: &[&'a str]) -> Option<Vec<&'a str>> {
+    lazy_static! {
+        static ref NUMBER: Regex = Regex::new(r"^[ \t]*(\d+)[.):]?[ \t]+").unwrap();
+    }
+    let mut previous: Option<u64> = None;
+    let mut numbered = 0;
+    let mut stripped = Vec::with_capacity(lines.len());
+    for line in lines {
+        if line.trim().is_empty() {
+            stripped.push(*line);
+            continue;
+        }
+        let captures = NUMBER.captures(line)?;
+        let number = captures[1].parse::<u64>().ok()?;
+        if previous.is_some_and(|previous| previous.checked_add(1) != Some(number)) {
+            return None;
+        }
+        previous = Some(number);
+        numbered += 1;
+        stripped.push(&line[captures[0].len()..]);
+    }
+    (numbered >= 2).then_some(stripped)
+}
+
+/// Remove leading line numbers like `1. `, `12) `, `3: ` or `4 `, as long
+/// as every line is numbered and they count up by one. Otherwise the numbers
+/// are part of the text, like a year in `"2024: revenue grew"`, and the text
+/// is returned as it is.
+pub fn unnumber_lines(text: &str) -> String {
+    let (lines, trailing) = split_lines(text);
+    match strip_counting_numbers(&lines) {
+        Some(stripped) => join_lines(&stripped, trailing),
+        None => text.to_owned(),
+    }
+}
+
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_dedupe_lines() {
+        let text = "a\nb\n\nA\n a  \nb\n\nc\n";
+        assert_eq!(dedupe_lines(text, &DedupeOptions::default()), "a\nb\n\nA\n a  \n\nc\n");
+
+        let options = DedupeOptions { ignore_case: true, ignore_whitespace: true, ..Default::default() };
+        assert_eq!(dedupe_lines(text, &options), "a\nb\n\n\nc\n");
+
+        let options = DedupeOptions { blank_lines: true, ..Default::default() };
+        assert_eq!(dedupe_lines(text, &options), "a\nb\n\nA\n a  \nc\n");
+    }
+
+    #[test]
+    pub fn test_dedupe_with_bounded_memory() {
+        let options = DedupeOptions { memory: Some(2), ..Default::default() };
+        assert_eq!(dedupe_lines("a\nb\na\nc\na\nb", &options), "a\nb\nc\na\nb");
+    }
+
+    #[test]
+    pub fn test_dedupe_stream() {
+        let input = "one\r\ntwo\r\none\r\nthree";
+        let mut output = vec![];
+        let removed = LineDeduplicator::new(&DedupeOptions::default()).stream(input.as_bytes(), &mut output).unwrap();
+        assert_eq!(removed, 1);
+        assert_eq!(String::from_utf8(output).unwrap(), "one\r\ntwo\r\nthree");
+
+        let options = DedupeOptions { memory: Some(8), hash_lines: true, ignore_case: true, ..Default::default() };
+        let mut output = vec![];
+        let removed = LineDeduplicator::new(&options).stream(input.as_bytes(), &mut output).unwrap();
+        assert_eq!(removed, 1);
+        assert_eq!(String::from_utf8(output).unwrap(), "one\r\ntwo\r\nthree");
+    }
+
+    #[test]
+    pub fn test_filter_lines() {
+        let text = "INFO start\nDEBUG x=1\ninfo done";
+        let filter = LineFilter { pattern: String::from("^info"), case_insensitive: true, ..Default::default() };
+        assert_eq!(filter.compile().unwrap().apply(text), "INFO start\ninfo done");
+
+        let filter = LineFilter { pattern: String::from("^DEBUG"), mode: FilterMode::Remove, ..Default::default() };
+        assert_eq!(filter.compile().unwrap().apply(text), "INFO start\ninfo done");
+    }
+
+    #[test]
+    pub fn test_limit_line_length() {
+        let options = LineLengthOptions { min: Some(2), max: Some(4) };
+        assert_eq!(limit_line_length("a\nabc\nabcde\nçà\n", &options), "abc\nçà\n");
+    }
+
+    #[test]
+    pub fn test_sort_lines() {
+        let text = "file10\nFile2\nfile1\nfile2";
+        assert_eq!(sort_lines(text, &SortOptions::default()).unwrap(), "File2\nfile1\nfile10\nfile2");
+        let options = SortOptions { order: SortOrder::Natural, ignore_case: true, ..Default::default() };
+        assert_eq!(sort_lines(text, &options).unwrap(), "file1\nFile2\nfile2\nfile10");
+        let options = SortOptions { order: SortOrder::Natural, reverse: true, ..Default::default() };
+        assert_eq!(sort_lines(text, &options).unwrap(), "file10\nfile2\nfile1\nFile2");
+    }
+
+    #[test]
+    pub fn test_sort_lines_by_collation() {
+        let options = SortOptions { order: SortOrder::Collation, ..Default::default() };
+        assert_eq!(sort_lines("Eric\némile\nEmile\nzoe", &options).unwrap(), "Emile\némile\nEric\nzoe");
+        assert_eq!(sort_lines("zebra\növer\nober", &options).unwrap(), "ober\növer\nzebra");
+
+        let options = SortOptions { order: SortOrder::Collation, locale: Some(String::from("sv")), ..Default::default() };
+        assert_eq!(sort_lines("zebra\növer\nober", &options).unwrap(), "ober\nzebra\növer");
+
+        let options = SortOptions { order: SortOrder::Collation, locale: Some(String::from("not a locale")), ..Default::default() };
+        assert!(matches!(sort_lines("a", &options), Err(Error::UnknownLocale(_))));
+    }
+
+    #[test]
+    pub fn test_number_and_unnumber_lines() {
+        let text = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\n";
+        let options = NumberingOptions { align: true, ..Default::default() };
+        let numbered = number_lines(text, &options);
+        assert!(numbered.starts_with(" 1. a\n 2. b\n"));
+        assert!(numbered.ends_with("10. j\n"));
+        assert_eq!(unnumber_lines(&numbered), text);
+        assert_eq!(unnumber_lines("1) one\n2: two\n3\tthree"), "one\ntwo\nthree");
+        assert_eq!(unnumber_lines("1) one\n2) two\n1.5 cups"), "1) one\n2) two\n1.5 cups");
+        assert_eq!(unnumber_lines("2024: revenue grew\n3) see above"), "2024: revenue grew\n3) see above");
+        assert_eq!(unnumber_lines("2024 was a good year\n3 apples"), "2024 was a good year\n3 apples");
+        assert_eq!(unnumber_lines("9 nine\n\n10\tten\n"), "nine\n\nten\n");
+        let options = NumberingOptions { separator: String::from(" "), ..Default::default() };
+        assert_eq!(unnumber_lines(&number_lines(text, &options)), text);
+    }
+}
@@ -0,0 +1,197 @@
+use pulldown_cmark::{Event, Options, Parser, Tag, TagEnd};
+use serde::{Deserialize, Serialize};
+
+use crate::html::Writer;
+
+/// Options for converting Markdown to plain text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MarkdownOptions {
+    /// Keep the contents of code blocks instead of dropping them.
+    pub keep_code_blocks: bool,
+    /// Keep tables as lines of tab-separated cells instead of dropping them.
+    pub keep_tables: bool,
+}
+
+fn parser(markdown: &str) -> Parser<'_> {
+    let options = Options::ENABLE_TABLES
+        | Options::ENABLE_FOOTNOTES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_YAML_STYLE_METADATA_BLOCKS;
+    Parser::new_ext(markdown, options)
+}
+
+/// Convert Markdown into plain text.
+///
+/// Headings, emphasis and link syntax are removed and their text kept.
+/// Images, raw HTML, footnote definitions and front matter are dropped,
+/// and so are code blocks and tables unless the options say otherwise.
+/// Paragraphs are separated by blank lines and list items put on their own lines.
+pub fn markdown_to_text(markdown: &str, options: &MarkdownOptions) -> String {
+    let mut writer = Writer::default();
+    // How many of the elements we're in have contents that are dropped.
+    let mut skipped = 0usize;
+    let mut lists = 0usize;
+    let mut in_code_block = false;
+    let mut first_cell = true;
+
+    for event in parser(markdown) {
+        match event {
+            Event::Start(tag) => {
+                let skip = match &tag {
+                    Tag::CodeBlock(_) => !options.keep_code_blocks,
+                    Tag::Table(_) => !options.keep_tables,
+                    Tag::Image { .. } | Tag::HtmlBlock | Tag::FootnoteDefinition(_) | Tag::MetadataBlock(_) => true,
+                    _ => false,
+                };
+                if skip || skipped > 0 {
+                    skipped += 1;
+                    continue;
+                }
+                match tag {
+                    Tag::List(_) => {
+                        writer.break_line(if lists > 0 { 1 } else { 2 });
+                        lists += 1;
+                    },
+                    Tag::Item | Tag::TableHead | Tag::TableRow | Tag::DefinitionListDefinition => {
+                        writer.break_line(1);
+                        first_cell = true;
+                    },
+                    Tag::TableCell => {
+                        if !first_cell {
+                            writer.write_raw("\t");
+                        }
+                        first_cell = false;
+                    },
+                    Tag::CodeBlock(_) => {
+                        writer.break_line(2);
+                        in_code_block = true;
+                    },
+                    Tag::Paragraph | Tag::Heading { .. } | Tag::BlockQuote(_) | Tag::Table(_) | Tag::DefinitionList => {
+                        writer.break_line(2);
+                    },
+                    _ => {},
+                }
+            },
+            Event::End(tag) => {
+                if skipped > 0 {
+                    skipped -= 1;
+                    continue;
+                }
+                match tag {
+                    TagEnd::List(_) => {
+                        lists -= 1;
+                        writer.break_line(if lists > 0 { 1 } else { 2 });
+                    },
+                    TagEnd::CodeBlock => {
+                        in_code_block = false;
+                        writer.break_line(2);
+                    },
+                    TagEnd::Paragraph | TagEnd::Heading(_) | TagEnd::BlockQuote(_) | TagEnd::Table => writer.break_line(2),
+                    TagEnd::TableHead | TagEnd::TableRow | TagEnd::DefinitionListTitle => writer.break_line(1),
+                    _ => {},
+                }
+            },
+            _ if skipped > 0 => {},
+            Event::Text(text) if in_code_block => writer.write_raw(text.trim_end_matches('\n')),
+            Event::Text(text) | Event::Code(text) | Event::InlineMath(text) => writer.write_raw(&text),
+            Event::DisplayMath(text) => {
+                writer.break_line(2);
+                writer.write_raw(&text);
+                writer.break_line(2);
+            },
+            Event::SoftBreak => writer.pending_space = true,
+            Event::HardBreak => writer.break_line(1),
+            Event::Rule => writer.break_line(2),
+            Event::Html(_) | Event::InlineHtml(_) | Event::FootnoteReference(_) | Event::TaskListMarker(_) => {},
+        }
+    }
+    writer.text
+}
+
+/// Run `clean` over the prose of a Markdown document and leave everything
+/// else, like its syntax, code blocks, inline code, URLs and raw HTML, as is.
+///
+/// `clean` is called once per run of plain text, so the text between two
+/// pieces of syntax (e.g. before and after an emphasized word) is cleaned
+/// separately. Text that contains escapes or entities is left untouched.
+pub fn clean_markdown_prose<F>(markdown: &str, clean: F) -> String
+where
+    F: Fn(&str) -> String
+{
+    let mut result = String::with_capacity(markdown.len());
+    let mut last = 0;
+    // How many of the elements we're in have contents that aren't prose.
+    let mut verbatim = 0usize;
+
+    for (event, range) in parser(markdown).into_offset_iter() {
+        match event {
+            Event::Start(Tag::CodeBlock(_) | Tag::HtmlBlock | Tag::MetadataBlock(_)) => verbatim += 1,
+            Event::End(TagEnd::CodeBlock | TagEnd::HtmlBlock | TagEnd::MetadataBlock(_)) => verbatim -= 1,
+            Event::Text(text) if verbatim == 0 && range.start >= last && markdown[range.clone()] == *text => {
+                let cleaned = clean(&text);
+                if cleaned != *text {
+                    result.push_str(&markdown[last..range.start]);
+                    result.push_str(&cleaned);
+                    last = range.end;
+                }
+            },
+            _ => {},
+        }
+    }
+    result.push_str(&markdown[last..]);
+    result
+}
+
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    const DOCUMENT: &str = "---
+title: Notes
+---
+
+# The *Title*
+
+Some **bold** and `code` with a [link](https://example.com \"Example\").
+![diagram](diagram.png)
+
+* one
+* two
+    1. three
+
+| a | b |
+|---|---|
+| 1 | 2 |
+
+```rust
+fn main() {}
+```
+
+> Quoted  <b>text</b>
+";
+
+    #[test]
+    pub fn test_markdown_to_text() {
+        assert_eq!(
+            markdown_to_text(DOCUMENT, &MarkdownOptions::default()),
+            "The Title\n\nSome bold and code with a link.\n\none\ntwo\nthree\n\nQuoted  text"
+        );
+    }
+
+    #[test]
+    pub fn test_keep_code_blocks_and_tables() {
+        let options = MarkdownOptions { keep_code_blocks: true, keep_tables: true };
+        let text = markdown_to_text(DOCUMENT, &options);
+        assert!(text.contains("three\n\na\tb\n1\t2\n\nfn main() {}\n\nQuoted"));
+    }
+
+    #[test]
+    pub fn test_clean_markdown_prose() {
+        let markdown = "# Hello,   world!\n\nKeep `a,   b` and [this!](https://x.io/a,b) \\*as is\\*.\n\n```\nx,   y\n```\n";
+        let cleaned = clean_markdown_prose(markdown, |s| s.replace(',', "").replace("   ", " "));
+        assert_eq!(cleaned, "# Hello world!\n\nKeep `a,   b` and [this!](https://x.io/a,b) \\*as is\\*.\n\n```\nx,   y\n```\n");
+    }
+}
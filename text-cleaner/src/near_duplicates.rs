@@ -0,0 +1,326 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+use crate::fraction::Fraction;
+use crate::pipeline::{Pipeline, PipelineSpec, Step};
+
+/// Options for finding near-duplicate documents.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NearDuplicateOptions {
+    /// Steps that clean each document before it is shingled.
+    pub steps: Vec<Step>,
+    /// How many consecutive words make up a shingle.
+    pub shingle_size: usize,
+    /// How many hash functions make up a MinHash signature.
+    pub num_hashes: usize,
+    /// How many bands signatures are split in for locality-sensitive hashing.
+    /// Documents become candidates when all the rows of one of their bands agree.
+    pub bands: usize,
+    /// The Jaccard similarity of their shingles above which (inclusive) two
+    /// documents are near-duplicates, from 0 to 1.
+    pub threshold: f64,
+    /// Seeds the hash functions, so that signatures are reproducible. They
+    /// don't depend on the Rust release either, so they can be stored.
+    pub seed: u64,
+}
+
+impl Default for NearDuplicateOptions {
+    fn default() -> Self {
+        Self {
+            steps: vec![],
+            shingle_size: 5,
+            num_hashes: 128,
+            bands: 32,
+            threshold: 0.8,
+            seed: 0,
+        }
+    }
+}
+
+/// Two documents found to be near-duplicates, by index.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SimilarPair {
+    pub first: usize,
+    pub second: usize,
+    pub jaccard: f64,
+}
+
+/// A group of documents that are near-duplicates of each other (transitively).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Cluster {
+    /// The documents of the cluster in order. The first one is the one that's kept.
+    pub documents: Vec<usize>,
+    /// The pairs that put the documents in the same cluster.
+    pub pairs: Vec<SimilarPair>,
+}
+
+/// Which documents were clustered together, and which ones are redundant.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct NearDuplicateReport {
+    pub documents: usize,
+    pub clusters: Vec<Cluster>,
+    /// Every document of a cluster but its first, in order.
+    pub duplicates: Vec<usize>,
+}
+
+impl NearDuplicateReport {
+    pub fn is_duplicate(&self, document: usize) -> bool {
+        self.duplicates.binary_search(&document).is_ok()
+    }
+
+    /// Keep the documents that aren't duplicates of an earlier one.
+    pub fn retain<T>(&self, documents: Vec<T>) -> Vec<T> {
+        documents
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _)| !self.is_duplicate(*i))
+            .map(|(_, document)| document)
+            .collect()
+    }
+}
+
+/// A MinHash signature along with the shingles it was computed from.
+#[derive(Debug, Clone)]
+pub struct Signature {
+    shingles: HashSet<u64>,
+    minimums: Vec<u64>,
+}
+
+impl Signature {
+    /// The exact Jaccard similarity of the shingles of two documents.
+    pub fn jaccard(&self, other: &Signature) -> f64 {
+        if self.shingles.is_empty() || other.shingles.is_empty() {
+            return 0.0;
+        }
+        let shared = self.shingles.intersection(&other.shingles).count();
+        shared as f64 / (self.shingles.len() + other.shingles.len() - shared) as f64
+    }
+
+    /// The Jaccard similarity as estimated from the MinHash signatures.
+    pub fn estimated_jaccard(&self, other: &Signature) -> f64 {
+        let agreeing = self.minimums.iter().zip(&other.minimums).filter(|(a, b)| a == b).count();
+        agreeing as f64 / self.minimums.len().max(1) as f64
+    }
+}
+
+/// The SplitMix64 finalizer, used to derive hash functions from a seed.
+fn mix(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^ (x >> 31)
+}
+
+/// 64-bit FNV-1a. Unlike `DefaultHasher`, its results are the same on every
+/// Rust release and platform.
+fn fnv1a<I: IntoIterator<Item = u8>>(bytes: I) -> u64 {
+    bytes
+        .into_iter()
+        .fold(0xCBF2_9CE4_8422_2325, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01B3))
+}
+
+/// The hash of a run of words, each ended by a byte that can't occur in UTF-8.
+fn hash_words(words: &[&str]) -> u64 {
+    fnv1a(words.iter().flat_map(|word| word.bytes().chain([0xFF])))
+}
+
+/// Finds near-duplicate documents with MinHash and LSH banding.
+pub struct NearDuplicateDetector {
+    pipeline: Pipeline,
+    shingle_size: usize,
+    rows: usize,
+    threshold: f64,
+    seeds: Vec<u64>,
+}
+
+impl NearDuplicateDetector {
+    /// Build a detector. When `num_hashes` isn't a multiple of `bands`,
+    /// the signatures are shortened to the nearest multiple.
+    pub fn new(options: &NearDuplicateOptions) -> Result<Self> {
+        if Fraction::new(options.threshold).is_none() {
+            return Err(Error::NotAFraction { option: "threshold", value: options.threshold });
+        }
+        let bands = options.bands.max(1);
+        let rows = (options.num_hashes / bands).max(1);
+        Ok(Self {
+            pipeline: Pipeline::from_spec(&PipelineSpec { steps: options.steps.clone(), ..Default::default() })?,
+            shingle_size: options.shingle_size.max(1),
+            rows,
+            threshold: options.threshold,
+            seeds: (0..bands * rows).map(|i| mix(options.seed ^ mix(i as u64))).collect(),
+        })
+    }
+
+    /// The shingles of a document: its runs of `shingle_size` lowercase words,
+    /// or all of its words if it has fewer than that.
+    fn shingles(&self, text: &str) -> HashSet<u64> {
+        let cleaned = self.pipeline.process(text).to_lowercase();
+        let words = cleaned.split(|c: char| !c.is_alphanumeric()).filter(|w| !w.is_empty()).collect::<Vec<_>>();
+        match words.len() {
+            0 => HashSet::new(),
+            n if n < self.shingle_size => HashSet::from([hash_words(&words)]),
+            _ => words.windows(self.shingle_size).map(hash_words).collect(),
+        }
+    }
+
+    pub fn signature(&self, text: &str) -> Signature {
+        let shingles = self.shingles(text);
+        let minimums = self
+            .seeds
+            .iter()
+            .map(|seed| shingles.iter().map(|shingle| mix(shingle ^ seed)).min().unwrap_or(u64::MAX))
+            .collect();
+        Signature { shingles, minimums }
+    }
+
+    /// Group the documents that are near-duplicates of each other.
+    pub fn find<S: AsRef<str>>(&self, documents: &[S]) -> NearDuplicateReport {
+        let signatures = documents.iter().map(|doc| self.signature(doc.as_ref())).collect::<Vec<_>>();
+
+        // Documents whose signatures agree on a whole band are candidates.
+        let mut buckets: HashMap<(usize, u64), Vec<usize>> = HashMap::new();
+        for (i, signature) in signatures.iter().enumerate() {
+            if signature.shingles.is_empty() {
+                continue;
+            }
+            for (band, rows) in signature.minimums.chunks(self.rows).enumerate() {
+                buckets.entry((band, fnv1a(rows.iter().flat_map(|row| row.to_le_bytes())))).or_default().push(i);
+            }
+        }
+        let mut candidates = buckets
+            .into_values()
+            .flat_map(|bucket| {
+                let pairs = bucket
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(n, &a)| bucket[n + 1..].iter().map(move |&b| (a, b)))
+                    .collect::<Vec<_>>();
+                pairs
+            })
+            .collect::<Vec<_>>();
+        candidates.sort_unstable();
+        candidates.dedup();
+
+        // Confirm the candidates and join them with union-find.
+        let mut parents = (0..documents.len()).collect::<Vec<_>>();
+        fn root(parents: &mut [usize], mut i: usize) -> usize {
+            while parents[i] != i {
+                parents[i] = parents[parents[i]];
+                i = parents[i];
+            }
+            i
+        }
+        let mut pairs = vec![];
+        for (first, second) in candidates {
+            let jaccard = signatures[first].jaccard(&signatures[second]);
+            if jaccard >= self.threshold {
+                let (a, b) = (root(&mut parents, first), root(&mut parents, second));
+                parents[a.max(b)] = a.min(b);
+                pairs.push(SimilarPair { first, second, jaccard });
+            }
+        }
+
+        let mut clusters: HashMap<usize, Cluster> = HashMap::new();
+        for pair in pairs {
+            let r = root(&mut parents, pair.first);
+            clusters.entry(r).or_insert_with(|| Cluster { documents: vec![], pairs: vec![] }).pairs.push(pair);
+        }
+        let mut clusters = clusters.into_values().collect::<Vec<_>>();
+        for cluster in &mut clusters {
+            let mut documents = cluster.pairs.iter().flat_map(|pair| [pair.first, pair.second]).collect::<Vec<_>>();
+            documents.sort_unstable();
+            documents.dedup();
+            cluster.documents = documents;
+        }
+        clusters.sort_by_key(|cluster| cluster.documents[0]);
+
+        let mut duplicates = clusters.iter().flat_map(|cluster| cluster.documents[1..].to_vec()).collect::<Vec<_>>();
+        duplicates.sort_unstable();
+        NearDuplicateReport { documents: documents.len(), clusters, duplicates }
+    }
+
+    /// Drop the documents that are near-duplicates of an earlier one,
+    /// along with a report of what was dropped.
+    pub fn dedupe<S: AsRef<str>>(&self, documents: Vec<S>) -> (Vec<S>, NearDuplicateReport) {
+        let report = self.find(&documents);
+        (report.retain(documents), report)
+    }
+}
+
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    const ARTICLE: &str = "The quick brown fox jumps over the lazy dog while the farmer watches \
+        from the porch and the sun sets slowly behind the hills of the quiet valley.";
+
+    fn detector(options: NearDuplicateOptions) -> NearDuplicateDetector {
+        NearDuplicateDetector::new(&options).unwrap()
+    }
+
+    #[test]
+    pub fn test_signatures() {
+        let detector = detector(NearDuplicateOptions { shingle_size: 3, ..Default::default() });
+        let a = detector.signature(ARTICLE);
+        let b = detector.signature(&ARTICLE.replace("lazy", "sleepy"));
+        let c = detector.signature("Completely unrelated text about databases and indexes.");
+        assert!(a.jaccard(&b) > 0.7 && a.jaccard(&b) < 1.0);
+        assert!((a.estimated_jaccard(&b) - a.jaccard(&b)).abs() < 0.2);
+        assert_eq!(a.jaccard(&c), 0.0);
+    }
+
+    #[test]
+    pub fn test_stable_hashes() {
+        assert_eq!(fnv1a(*b"a"), 0xAF63_DC4C_8601_EC8C);
+        assert_eq!(hash_words(&["a", "b"]), fnv1a(*b"a\xFFb\xFF"));
+    }
+
+    #[test]
+    pub fn test_threshold_must_be_a_fraction() {
+        for threshold in [f64::NAN, -0.1, 1.5] {
+            let options = NearDuplicateOptions { threshold, ..Default::default() };
+            assert!(matches!(NearDuplicateDetector::new(&options), Err(Error::NotAFraction { option: "threshold", .. })));
+        }
+    }
+
+    #[test]
+    pub fn test_find_clusters() {
+        let documents = vec![
+            String::from(ARTICLE),
+            String::from("Nothing to see here, just a short note."),
+            format!("Home | About | Contact\n{}", ARTICLE.to_uppercase()),
+            ARTICLE.replace("quiet", "peaceful"),
+            String::new(),
+            String::new(),
+        ];
+        let detector = detector(NearDuplicateOptions { shingle_size: 3, threshold: 0.7, ..Default::default() });
+        let (kept, report) = detector.dedupe(documents);
+
+        assert_eq!(report.documents, 6);
+        assert_eq!(report.clusters.len(), 1);
+        assert_eq!(report.clusters[0].documents, vec![0, 2, 3]);
+        assert!(report.clusters[0].pairs.iter().all(|pair| pair.jaccard >= 0.7));
+        assert_eq!(report.duplicates, vec![2, 3]);
+        assert_eq!(kept.len(), 4);
+        assert_eq!(kept[1], "Nothing to see here, just a short note.");
+    }
+
+    #[test]
+    pub fn test_cleaning_before_shingling() {
+        let documents = ["Price: 10 EUR, see https://a.example", "Price: 10 EUR, see https://b.example"];
+        let strict = detector(NearDuplicateOptions { shingle_size: 2, threshold: 0.9, ..Default::default() });
+        assert!(strict.find(&documents).clusters.is_empty());
+
+        let options = NearDuplicateOptions {
+            steps: vec![Step::RemoveAllUrls],
+            shingle_size: 2,
+            threshold: 0.9,
+            ..Default::default()
+        };
+        assert_eq!(detector(options).find(&documents).duplicates, vec![1]);
+    }
+}
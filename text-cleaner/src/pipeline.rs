@@ -0,0 +1,670 @@
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use crate::alphanumeric::{AlphanumericFilter, AlphanumericOptions};
+use crate::boilerplate::{remove_page_numbers, remove_paged_boilerplate, BoilerplateOptions};
+use crate::case::{CaseConverter, CaseOptions};
+use crate::clean::Clean;
+use crate::dictionary::DictionaryRule;
+use crate::emoji::{replace_emojis, EmojiOptions};
+use crate::emoticon::{replace_emoticons, EmoticonOptions};
+use crate::error::Result;
+use crate::folding::{remove_accents, remove_non_ascii, FoldingOptions};
+use crate::fraction::Fraction;
+use crate::html::{clean_text_nodes, html_to_text, HtmlOptions, HtmlTextOptions};
+use crate::language::{map_paragraphs, Language, LanguageDetector};
+use crate::lines::{
+    dedupe_lines, limit_line_length, number_lines, unnumber_lines, DedupeOptions, LineFilter,
+    LineLengthOptions, LineSorter, NumberingOptions, SortOptions,
+};
+use crate::markdown::{clean_markdown_prose, markdown_to_text, MarkdownOptions};
+use crate::protect::{Protect, Protector};
+use crate::punctuation::{PunctuationFilter, PunctuationOptions};
+use crate::repair::{RepairOptions, Repairer};
+use crate::replace::ReplaceRule;
+use crate::scope::Scope;
+use crate::script::{ScriptFilter, ScriptOptions};
+use crate::segment::{SegmentOptions, Segmenter};
+use crate::stemming::{LemmaOptions, Lemmatizer, StemOptions, Stemmer};
+use crate::stopwords::{StopwordOptions, Stopwords};
+use crate::transliterate::{TransliterationOptions, Transliterator};
+use crate::wrap::{unwrap_text, wrap_text, WrapOptions};
+
+/// A single operation that a text goes through.
+pub type Operation = Box<dyn Fn(&str) -> String + Send + Sync>;
+
+/// A serializable description of one step of a pipeline.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum Step {
+    Trim,
+    RemoveLeadingSpaces,
+    RemoveTrailingSpaces,
+    RemoveEmptyLines,
+    ConvertMultipleSpacesToSingle,
+    RemoveAllEmojis,
+    ReplaceEmojis(EmojiOptions),
+    ReplaceEmoticons(EmoticonOptions),
+    RemovePunctuationMarks(PunctuationOptions),
+    RemoveLetterAccents(FoldingOptions),
+    NormalizeUnicodeCharacters,
+    RemoveNonAsciiCharacters(FoldingOptions),
+    RemoveNonAlphanumericCharacters(AlphanumericOptions),
+    FilterScripts(ScriptOptions),
+    Transliterate(TransliterationOptions),
+    RemoveAllEmails,
+    RemoveAllUrls,
+    HtmlToText(HtmlOptions),
+    /// Run `steps` over the text nodes of an HTML document, keeping its markup.
+    HtmlText {
+        steps: Vec<Step>,
+        #[serde(flatten)]
+        options: HtmlTextOptions,
+    },
+    MarkdownToText(MarkdownOptions),
+    /// Run `steps` over the prose of a Markdown document, keeping its syntax and code.
+    MarkdownProse {
+        steps: Vec<Step>,
+    },
+    /// Run `steps` only over the lines or paragraphs in `scope`.
+    Scoped {
+        scope: Scope,
+        steps: Vec<Step>,
+    },
+    Replace(ReplaceRule),
+    Dictionary(DictionaryRule),
+    DedupeLines(DedupeOptions),
+    FilterLines(LineFilter),
+    LimitLineLength(LineLengthOptions),
+    SortLines(SortOptions),
+    NumberLines(NumberingOptions),
+    UnnumberLines,
+    /// Remove the lines that repeat across the pages of a text, which are
+    /// separated by form feeds.
+    RemoveBoilerplate(BoilerplateOptions),
+    RemovePageNumbers,
+    /// Repair text extracted from PDFs or OCR: ligatures, hyphenation and line wrapping.
+    Repair(RepairOptions),
+    Wrap(WrapOptions),
+    /// Join the wrapped lines of each paragraph back into one.
+    Unwrap,
+    /// Split the text into sentences or words and write them out as chosen.
+    /// Best used as the last step, since it changes the text's layout.
+    Segment(SegmentOptions),
+    RemoveStopwords(StopwordOptions),
+    Stem(StemOptions),
+    Lemmatize(LemmaOptions),
+    /// Run the steps for the language the text (or each paragraph) is
+    /// detected to be in, or `default` for other languages and when the
+    /// detection is less confident than `min_confidence`.
+    ByLanguage {
+        routes: BTreeMap<Language, Vec<Step>>,
+        #[serde(default)]
+        default: Vec<Step>,
+        #[serde(default)]
+        per_paragraph: bool,
+        #[serde(default)]
+        min_confidence: Fraction,
+    },
+    /// Drop the text (or each paragraph) that isn't detected to be in one of
+    /// `languages` with at least `min_confidence`.
+    FilterLanguages {
+        languages: Vec<Language>,
+        #[serde(default)]
+        per_paragraph: bool,
+        #[serde(default)]
+        min_confidence: Fraction,
+    },
+    ChangeCase(CaseOptions),
+}
+
+/// Wrap a `Clean` method into an [`Operation`].
+macro_rules! clean_op {
+    ($func_name: ident) => {
+        Box::new(|s: &str| Clean::$func_name(&s.to_owned()))
+    };
+}
+
+impl Step {
+    /// Turn the step into an operation, compiling any
+    /// user-supplied patterns.
+    pub fn compile(&self) -> Result<Operation> {
+        let op: Operation = match self {
+            Step::Trim => clean_op!(trim),
+            Step::RemoveLeadingSpaces => clean_op!(remove_leading_spaces),
+            Step::RemoveTrailingSpaces => clean_op!(remove_trailing_spaces),
+            Step::RemoveEmptyLines => clean_op!(remove_empty_lines),
+            Step::ConvertMultipleSpacesToSingle => clean_op!(convert_multiple_spaces_to_single),
+            Step::RemoveAllEmojis => clean_op!(remove_all_emojis),
+            Step::ReplaceEmojis(options) => {
+                let options = options.clone();
+                Box::new(move |s: &str| replace_emojis(s, &options))
+            },
+            Step::ReplaceEmoticons(options) => {
+                let options = options.clone();
+                Box::new(move |s: &str| replace_emoticons(s, &options))
+            },
+            Step::RemovePunctuationMarks(options) => {
+                let filter = PunctuationFilter::new(options);
+                Box::new(move |s: &str| filter.apply(s))
+            },
+            Step::RemoveLetterAccents(options) => {
+                let options = *options;
+                Box::new(move |s: &str| remove_accents(s, &options))
+            },
+            Step::NormalizeUnicodeCharacters => clean_op!(normalize_unicode_characters),
+            Step::RemoveNonAsciiCharacters(options) => {
+                let options = *options;
+                Box::new(move |s: &str| remove_non_ascii(s, &options))
+            },
+            Step::RemoveNonAlphanumericCharacters(options) => {
+                let filter = AlphanumericFilter::new(options)?;
+                Box::new(move |s: &str| filter.apply(s))
+            },
+            Step::FilterScripts(options) => {
+                let filter = ScriptFilter::new(options)?;
+                Box::new(move |s: &str| filter.apply(s))
+            },
+            Step::Transliterate(options) => {
+                let transliterator = Transliterator::new(options)?;
+                Box::new(move |s: &str| transliterator.apply(s))
+            },
+            Step::RemoveAllEmails => clean_op!(remove_all_emails),
+            Step::RemoveAllUrls => clean_op!(remove_all_urls),
+            Step::HtmlToText(options) => {
+                let options = options.clone();
+                Box::new(move |s: &str| html_to_text(s, &options))
+            },
+            Step::HtmlText { steps, options } => {
+                let pipeline = Pipeline::from_spec(&PipelineSpec { steps: steps.clone(), ..Default::default() })?;
+                let options = options.clone();
+                Box::new(move |s: &str| pipeline.process_html(s, &options))
+            },
+            Step::MarkdownToText(options) => {
+                let options = *options;
+                Box::new(move |s: &str| markdown_to_text(s, &options))
+            },
+            Step::MarkdownProse { steps } => {
+                let pipeline = Pipeline::from_spec(&PipelineSpec { steps: steps.clone(), ..Default::default() })?;
+                Box::new(move |s: &str| pipeline.process_markdown(s))
+            },
+            Step::Scoped { scope, steps } => {
+                let scope = scope.compile()?;
+                let pipeline = Pipeline::from_spec(&PipelineSpec { steps: steps.clone(), ..Default::default() })?;
+                Box::new(move |s: &str| scope.apply(s, |text| pipeline.process(text)))
+            },
+            Step::Replace(rule) => {
+                let rule = rule.compile()?;
+                Box::new(move |s: &str| rule.apply(s))
+            },
+            Step::Dictionary(rule) => {
+                let dictionary = rule.compile()?;
+                Box::new(move |s: &str| dictionary.replace(s))
+            },
+            Step::DedupeLines(options) => {
+                let options = *options;
+                Box::new(move |s: &str| dedupe_lines(s, &options))
+            },
+            Step::FilterLines(filter) => {
+                let filter = filter.compile()?;
+                Box::new(move |s: &str| filter.apply(s))
+            },
+            Step::LimitLineLength(options) => {
+                let options = *options;
+                Box::new(move |s: &str| limit_line_length(s, &options))
+            },
+            Step::SortLines(options) => {
+                let sorter = LineSorter::new(options)?;
+                Box::new(move |s: &str| sorter.apply(s))
+            },
+            Step::NumberLines(options) => {
+                let options = options.clone();
+                Box::new(move |s: &str| number_lines(s, &options))
+            },
+            Step::UnnumberLines => Box::new(unnumber_lines),
+            Step::RemoveBoilerplate(options) => {
+                let options = *options;
+                Box::new(move |s: &str| remove_paged_boilerplate(s, &options))
+            },
+            Step::RemovePageNumbers => Box::new(remove_page_numbers),
+            Step::Repair(options) => {
+                let repairer = Repairer::new(options)?;
+                Box::new(move |s: &str| repairer.apply(s))
+            },
+            Step::Wrap(options) => {
+                let options = *options;
+                Box::new(move |s: &str| wrap_text(s, &options))
+            },
+            Step::Unwrap => Box::new(unwrap_text),
+            Step::Segment(options) => {
+                let segmenter = Segmenter::new(options);
+                Box::new(move |s: &str| segmenter.format(s))
+            },
+            Step::RemoveStopwords(options) => {
+                let stopwords = Stopwords::new(options)?;
+                Box::new(move |s: &str| stopwords.remove(s))
+            },
+            Step::Stem(options) => {
+                let stemmer = Stemmer::new(options);
+                Box::new(move |s: &str| stemmer.apply(s))
+            },
+            Step::Lemmatize(options) => {
+                let lemmatizer = Lemmatizer::new(options)?;
+                Box::new(move |s: &str| lemmatizer.apply(s))
+            },
+            Step::ByLanguage { routes, default, per_paragraph, min_confidence } => {
+                let mut pipelines = HashMap::new();
+                for (language, steps) in routes {
+                    pipelines.insert(*language, Pipeline::from_spec(&PipelineSpec { steps: steps.clone(), ..Default::default() })?);
+                }
+                let default = Pipeline::from_spec(&PipelineSpec { steps: default.clone(), ..Default::default() })?;
+                let detector = LanguageDetector::new();
+                let (per_paragraph, min_confidence) = (*per_paragraph, *min_confidence);
+                Box::new(move |s: &str| map_paragraphs(s, per_paragraph, |text| {
+                    let pipeline = detector
+                        .detect(text)
+                        .filter(|detection| detection.confidence >= min_confidence.get())
+                        .and_then(|detection| pipelines.get(&detection.language))
+                        .unwrap_or(&default);
+                    Some(pipeline.process(text))
+                }))
+            },
+            Step::FilterLanguages { languages, per_paragraph, min_confidence } => {
+                let languages = languages.clone();
+                let detector = LanguageDetector::new();
+                let (per_paragraph, min_confidence) = (*per_paragraph, *min_confidence);
+                Box::new(move |s: &str| map_paragraphs(s, per_paragraph, |text| {
+                    detector
+                        .detect(text)
+                        .is_some_and(|detection| detection.confidence >= min_confidence.get() && languages.contains(&detection.language))
+                        .then(|| text.to_owned())
+                }))
+            },
+            Step::ChangeCase(options) => {
+                let converter = CaseConverter::new(options);
+                Box::new(move |s: &str| converter.apply(s))
+            },
+        };
+        Ok(op)
+    }
+}
+
+/// An ordered list of steps that can be stored and loaded as JSON.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct PipelineSpec {
+    pub steps: Vec<Step>,
+    /// Regions of the text that pass through every step unchanged.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub protect: Vec<Protect>,
+}
+
+impl PipelineSpec {
+    pub fn from_json(json: &str) -> Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("A pipeline spec is always serializable.")
+    }
+}
+
+/// A compiled sequence of operations.
+#[derive(Default)]
+pub struct Pipeline {
+    operations: Vec<Operation>,
+    protector: Protector,
+}
+
+impl Pipeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_spec(spec: &PipelineSpec) -> Result<Self> {
+        let mut pipeline = Self::new();
+        pipeline.protector = Protector::new(&spec.protect)?;
+        for step in &spec.steps {
+            pipeline.operations.push(step.compile()?);
+        }
+        Ok(pipeline)
+    }
+
+    pub fn chain<F>(&mut self, f: F)
+    where
+        F: Fn(&str) -> String + Send + Sync + 'static
+    {
+        self.operations.push(Box::new(f));
+    }
+
+    pub fn len(&self) -> usize {
+        self.operations.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+
+    pub fn process(&self, text: &str) -> String {
+        match self.protector.is_empty() {
+            true => self.run(text),
+            false => self.protector.apply(text, |text| self.run(text)),
+        }
+    }
+
+    fn run(&self, text: &str) -> String {
+        let mut result = text.to_owned();
+        for op in &self.operations {
+            result = op(&result);
+        }
+        result
+    }
+
+    /// Process the text nodes of an HTML document, leaving its markup as it is.
+    pub fn process_html(&self, html: &str, options: &HtmlTextOptions) -> String {
+        clean_text_nodes(html, options, |text| self.process(text))
+    }
+
+    /// Process the prose of a Markdown document, leaving its syntax and code as they are.
+    pub fn process_markdown(&self, markdown: &str) -> String {
+        clean_markdown_prose(markdown, |text| self.process(text))
+    }
+}
+
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_pipeline_from_json() {
+        let spec = PipelineSpec::from_json(r#"{
+            "steps": [
+                {"op": "replace", "pattern": "[deleted]"},
+                {"op": "replace", "pattern": "Fig\\. (\\d+)", "replacement": "Figure $1", "regex": true},
+                {"op": "convert_multiple_spaces_to_single"},
+                {"op": "trim"}
+            ]
+        }"#).unwrap();
+        let pipeline = Pipeline::from_spec(&spec).unwrap();
+        assert_eq!(pipeline.len(), 4);
+        assert_eq!(pipeline.process(" [deleted]  see Fig. 3 "), "see Figure 3");
+        assert_eq!(PipelineSpec::from_json(&spec.to_json()).unwrap(), spec);
+    }
+
+    #[test]
+    pub fn test_punctuation_step() {
+        let spec = PipelineSpec::from_json(r#"{
+            "steps": [
+                {"op": "remove_punctuation_marks"},
+                {"op": "remove_punctuation_marks", "categories": ["Pd"], "keep_intraword_hyphens": true}
+            ]
+        }"#).unwrap();
+        assert_eq!(spec.steps[0], Step::RemovePunctuationMarks(PunctuationOptions::default()));
+        let pipeline = Pipeline::from_spec(&spec).unwrap();
+        assert_eq!(pipeline.process("well-known (really)!"), "wellknown really");
+    }
+
+    #[test]
+    pub fn test_alphanumeric_step() {
+        let spec = PipelineSpec::from_json(r#"{
+            "steps": [
+                {"op": "remove_non_alphanumeric_characters", "preserve_words": true, "scripts": ["Latin"]}
+            ]
+        }"#).unwrap();
+        let pipeline = Pipeline::from_spec(&spec).unwrap();
+        assert_eq!(pipeline.process("hello, мир world!"), "hello world");
+    }
+
+    #[test]
+    pub fn test_filter_scripts_step() {
+        let spec = PipelineSpec::from_json(r#"{
+            "steps": [
+                {"op": "filter_scripts", "mode": "keep", "scripts": ["Latin", "Cyrillic"], "common": "follow"}
+            ]
+        }"#).unwrap();
+        let pipeline = Pipeline::from_spec(&spec).unwrap();
+        assert_eq!(pipeline.process("да, yes. 是的。"), "да, yes. ");
+    }
+
+    #[test]
+    pub fn test_transliterate_step() {
+        let spec = PipelineSpec::from_json(r#"{
+            "steps": [
+                {"op": "transliterate", "scripts": {"Cyrillic": "iso9", "Han": "keep"}}
+            ]
+        }"#).unwrap();
+        let pipeline = Pipeline::from_spec(&spec).unwrap();
+        assert_eq!(pipeline.process("Жук 北京 Αθήνα"), "Žuk 北京 Athena");
+    }
+
+    #[test]
+    pub fn test_html_to_text_step() {
+        let spec = PipelineSpec::from_json(r#"{
+            "steps": [
+                {"op": "html_to_text", "bullet": "* "},
+                {"op": "remove_empty_lines"}
+            ]
+        }"#).unwrap();
+        let pipeline = Pipeline::from_spec(&spec).unwrap();
+        assert_eq!(pipeline.process("<h2>To do</h2><ul><li>eat</li><li>sleep</li></ul>"), "To do\n* eat\n* sleep");
+    }
+
+    #[test]
+    pub fn test_html_text_step() {
+        let spec = PipelineSpec::from_json(r#"{
+            "steps": [
+                {"op": "html_text", "attributes": ["title"], "steps": [
+                    {"op": "remove_all_emails"},
+                    {"op": "convert_multiple_spaces_to_single"}
+                ]}
+            ]
+        }"#).unwrap();
+        let pipeline = Pipeline::from_spec(&spec).unwrap();
+        assert_eq!(
+            pipeline.process(r#"<p title="mail a@b.com">Write   to a@b.com</p><code>a@b.com</code>"#),
+            r#"<p title="mail ">Write to </p><code>a@b.com</code>"#
+        );
+        assert_eq!(PipelineSpec::from_json(&spec.to_json()).unwrap(), spec);
+    }
+
+    #[test]
+    pub fn test_markdown_steps() {
+        let spec = PipelineSpec::from_json(r#"{
+            "steps": [
+                {"op": "markdown_prose", "steps": [{"op": "remove_punctuation_marks"}]}
+            ]
+        }"#).unwrap();
+        let pipeline = Pipeline::from_spec(&spec).unwrap();
+        let markdown = "# Hi, there!\n\n- see [docs.rs](https://docs.rs), or `cargo doc`.\n";
+        assert_eq!(pipeline.process(markdown), "# Hi there\n\n- see [docsrs](https://docs.rs) or `cargo doc`\n");
+
+        let spec = PipelineSpec::from_json(r#"{"steps": [{"op": "markdown_to_text"}]}"#).unwrap();
+        let pipeline = Pipeline::from_spec(&spec).unwrap();
+        assert_eq!(pipeline.process(markdown), "Hi, there!\n\nsee docs.rs, or cargo doc.");
+    }
+
+    #[test]
+    pub fn test_scoped_steps() {
+        let spec = PipelineSpec::from_json(r#"{
+            "steps": [
+                {"op": "scoped", "scope": {"kind": "lines_matching", "pattern": "^>"}, "steps": [
+                    {"op": "remove_punctuation_marks"}
+                ]},
+                {"op": "scoped", "scope": {"kind": "first_lines", "count": 1}, "steps": [
+                    {"op": "replace", "pattern": "Re: ", "replacement": ""}
+                ]}
+            ]
+        }"#).unwrap();
+        let pipeline = Pipeline::from_spec(&spec).unwrap();
+        assert_eq!(pipeline.process("Re: Hi!\n> What's up?\nNot much, you?"), "Hi!\n Whats up\nNot much, you?");
+        assert_eq!(PipelineSpec::from_json(&spec.to_json()).unwrap(), spec);
+    }
+
+    #[test]
+    pub fn test_line_steps() {
+        let spec = PipelineSpec::from_json(r#"{
+            "steps": [
+                {"op": "unnumber_lines"},
+                {"op": "filter_lines", "pattern": "^#", "mode": "remove"},
+                {"op": "limit_line_length", "min": 2},
+                {"op": "dedupe_lines", "ignore_case": true},
+                {"op": "sort_lines", "order": "natural"},
+                {"op": "number_lines", "separator": ": "}
+            ]
+        }"#).unwrap();
+        let pipeline = Pipeline::from_spec(&spec).unwrap();
+        assert_eq!(pipeline.process("1. item 10\n2. # comment\n3. x\n4. item 9\n5. Item 10\n"), "1: item 9\n2: item 10\n");
+    }
+
+    #[test]
+    pub fn test_boilerplate_steps() {
+        let spec = PipelineSpec::from_json(r#"{
+            "steps": [
+                {"op": "remove_boilerplate", "min_frequency": 1.0, "page_numbers": false},
+                {"op": "remove_page_numbers"}
+            ]
+        }"#).unwrap();
+        let pipeline = Pipeline::from_spec(&spec).unwrap();
+        let text = "Header\nIntro\n- 1 -\x0CHeader\nBody\n- 2 -";
+        assert_eq!(pipeline.process(text), "Intro\x0CBody");
+    }
+
+    #[test]
+    pub fn test_replace_emojis_step() {
+        let spec = PipelineSpec::from_json(r#"{
+            "steps": [
+                {"op": "replace_emojis", "action": "description", "except": ["flags"]}
+            ]
+        }"#).unwrap();
+        let pipeline = Pipeline::from_spec(&spec).unwrap();
+        assert_eq!(pipeline.process("🇨🇦❤️"), "🇨🇦 red heart");
+    }
+
+    #[test]
+    pub fn test_replace_emoticons_step() {
+        let spec = PipelineSpec::from_json(r#"{
+            "steps": [
+                {"op": "replace_emoticons", "action": "token", "token": "EMO_{name}"},
+                {"op": "remove_punctuation_marks"}
+            ]
+        }"#).unwrap();
+        let pipeline = Pipeline::from_spec(&spec).unwrap();
+        assert_eq!(pipeline.process("ok ;) (ノಠ益ಠ)ノ彡┻━┻"), "ok EMO_wink EMO_table_flip");
+    }
+
+    #[test]
+    pub fn test_dictionary_step() {
+        let spec = PipelineSpec::from_json(r#"{
+            "steps": [
+                {"op": "dictionary", "entries": {"btw": "by the way", "imo": "in my opinion"}, "whole_word": true, "case_insensitive": true}
+            ]
+        }"#).unwrap();
+        let pipeline = Pipeline::from_spec(&spec).unwrap();
+        assert_eq!(pipeline.process("BTW, imo limo"), "by the way, in my opinion limo");
+    }
+
+    #[test]
+    pub fn test_pipeline_rejects_bad_rules() {
+        let spec = PipelineSpec {
+            steps: vec![Step::Replace(ReplaceRule::regex("(unclosed", ""))],
+            ..Default::default()
+        };
+        assert!(Pipeline::from_spec(&spec).is_err());
+
+        let spec = PipelineSpec {
+            protect: vec![Protect::Regex { pattern: String::from("[z-a]"), case_insensitive: false, multiline: false }],
+            ..Default::default()
+        };
+        assert!(Pipeline::from_spec(&spec).is_err());
+    }
+
+    #[test]
+    pub fn test_protected_regions() {
+        let spec = PipelineSpec::from_json(r#"{
+            "steps": [
+                {"op": "remove_all_urls"},
+                {"op": "convert_multiple_spaces_to_single"},
+                {"op": "trim"}
+            ],
+            "protect": [
+                {"kind": "code_blocks"},
+                {"kind": "regex", "pattern": "ticket #\\d+"}
+            ]
+        }"#).unwrap();
+        let pipeline = Pipeline::from_spec(&spec).unwrap();
+        let text = "  See   https://x.io   for  ticket #12:\n```\ncurl  https://x.io/api\n```\n  ";
+        assert_eq!(pipeline.process(text), "See for ticket #12:\n```\ncurl  https://x.io/api\n```\n");
+        assert_eq!(PipelineSpec::from_json(&spec.to_json()).unwrap(), spec);
+    }
+
+    #[test]
+    pub fn test_repair_step() {
+        let spec = PipelineSpec::from_json(r#"{"steps": [{"op": "repair", "unwrap_lines": false}]}"#).unwrap();
+        let pipeline = Pipeline::from_spec(&spec).unwrap();
+        assert_eq!(pipeline.process("ﬁnal experi-\nment"), "final experiment");
+        let spec = PipelineSpec::from_json(r#"{"steps": [{"op": "repair", "word_list": "/nonexistent/words.txt"}]}"#).unwrap();
+        assert!(Pipeline::from_spec(&spec).is_err());
+    }
+
+    #[test]
+    pub fn test_segment_step() {
+        let spec = PipelineSpec::from_json(r#"{"steps": [
+            {"op": "remove_all_urls"},
+            {"op": "segment", "language": "de", "output": "tokens"}
+        ]}"#).unwrap();
+        let pipeline = Pipeline::from_spec(&spec).unwrap();
+        assert_eq!(pipeline.process("Siehe z.B. https://x.io hier."), "Siehe z.B. hier .");
+    }
+
+    #[test]
+    pub fn test_remove_stopwords_step() {
+        let spec = PipelineSpec::from_json(r#"{"steps": [
+            {"op": "remove_stopwords", "languages": ["en", "fr"], "words": ["fox"]}
+        ]}"#).unwrap();
+        let pipeline = Pipeline::from_spec(&spec).unwrap();
+        assert_eq!(pipeline.process("The quick fox et le chien"), "quick chien");
+    }
+
+    #[test]
+    pub fn test_stem_and_lemmatize_steps() {
+        let spec = PipelineSpec::from_json(r#"{"steps": [
+            {"op": "lemmatize", "entries": {"geese": "goose"}},
+            {"op": "stem", "language": "en"}
+        ]}"#).unwrap();
+        let pipeline = Pipeline::from_spec(&spec).unwrap();
+        assert_eq!(pipeline.process("Geese, flying south."), "Goos, fli south.");
+    }
+
+    #[test]
+    pub fn test_language_steps() {
+        let text = "The weather was lovely today, so we walked along the river.\n\n\
+            Das Wetter war heute sehr schön, deshalb sind wir am Fluss spazieren gegangen.";
+        let spec = PipelineSpec::from_json(r#"{"steps": [{
+            "op": "by_language",
+            "per_paragraph": true,
+            "routes": {
+                "en": [{"op": "remove_stopwords", "languages": ["en"]}],
+                "de": [{"op": "stem", "language": "de"}]
+            }
+        }]}"#).unwrap();
+        let pipeline = Pipeline::from_spec(&spec).unwrap();
+        assert_eq!(
+            pipeline.process(text),
+            "weather lovely today, walked along river.\n\nDas Wett war heut sehr schon, deshalb sind wir am Fluss spazi gegang."
+        );
+
+        let spec = PipelineSpec::from_json(r#"{"steps": [
+            {"op": "filter_languages", "languages": ["de"], "per_paragraph": true}
+        ]}"#).unwrap();
+        assert!(Pipeline::from_spec(&spec).unwrap().process(text).starts_with("Das Wetter"));
+    }
+
+    #[test]
+    pub fn test_change_case_step() {
+        let spec = PipelineSpec::from_json(r#"{"steps": [{"op": "change_case", "case": "upper", "locale": "tr"}]}"#).unwrap();
+        assert_eq!(Pipeline::from_spec(&spec).unwrap().process("istanbul"), "İSTANBUL");
+        let spec = PipelineSpec::from_json(r#"{"steps": [{"op": "change_case", "case": "de_shout", "acronyms": ["EU"]}]}"#).unwrap();
+        assert_eq!(Pipeline::from_spec(&spec).unwrap().process("THE EU AGREED."), "The EU agreed.");
+    }
+}
@@ -0,0 +1,277 @@
+use std::ops::Range;
+
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+use crate::replace::{CompiledRule, ReplaceRule};
+
+/// A kind of region of a text that cleaning must leave untouched.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Protect {
+    /// Fenced code blocks (```` ``` ```` or `~~~`) and blocks of lines
+    /// indented by four spaces or a tab, like stack traces.
+    CodeBlocks,
+    /// Text between backticks.
+    InlineCode,
+    /// Text between double quotes, curly quotes or guillemets.
+    Quotes,
+    /// The matches of a regular expression.
+    Regex {
+        pattern: String,
+        #[serde(default)]
+        case_insensitive: bool,
+        #[serde(default)]
+        multiline: bool,
+    },
+    /// A byte range, widened to character boundaries if needed.
+    Range { start: usize, end: usize },
+}
+
+lazy_static! {
+    static ref FENCE: Regex = Regex::new(r"^ {0,3}(`{3,}|~{3,})").unwrap();
+    static ref INDENTED: Regex = Regex::new(r"^( {4}|\t)\s*\S").unwrap();
+    static ref INLINE_CODE: Regex = Regex::new(r"``[^\n]+?``|`[^`\n]+`").unwrap();
+    static ref QUOTES: Regex = Regex::new(r#""[^"\n]*"|“[^”]*”|„[^“”]*[“”]|«[^»]*»"#).unwrap();
+}
+
+/// The byte range of each line of `text`, including its newline.
+fn lines(text: &str) -> impl Iterator<Item = Range<usize>> + '_ {
+    text.split_inclusive('\n').scan(0, |start, line| {
+        let range = *start..*start + line.len();
+        *start = range.end;
+        Some(range)
+    })
+}
+
+fn code_blocks(text: &str) -> Vec<Range<usize>> {
+    let mut blocks: Vec<Range<usize>> = vec![];
+    // The fence of the open fenced block, and where it started.
+    let mut fence: Option<(String, usize)> = None;
+    let mut indented: Option<Range<usize>> = None;
+
+    for line in lines(text) {
+        let content = &text[line.clone()];
+        if let Some((open, start)) = &fence {
+            let closing = content.trim();
+            if closing.len() >= open.len() && closing.chars().all(|c| open.starts_with(c)) {
+                blocks.push(*start..line.end);
+                fence = None;
+            }
+            continue;
+        }
+        if let Some(captures) = FENCE.captures(content) {
+            blocks.extend(indented.take());
+            fence = Some((captures[1].to_owned(), line.start));
+            continue;
+        }
+        match INDENTED.is_match(content) {
+            true => indented = Some(indented.map_or(line.clone(), |block| block.start..line.end)),
+            false => blocks.extend(indented.take()),
+        }
+    }
+    blocks.extend(indented);
+    // An unclosed fence runs to the end of the text.
+    blocks.extend(fence.map(|(_, start)| start..text.len()));
+    blocks
+}
+
+#[derive(Debug)]
+enum Protection {
+    CodeBlocks,
+    InlineCode,
+    Quotes,
+    Regex(CompiledRule),
+    Range(Range<usize>),
+}
+
+/// The first private use character used to stand in for a protected region.
+const PLACEHOLDER: u32 = 0xF0000;
+
+/// How many placeholders there are (the supplementary private use area A).
+const PLACEHOLDERS: usize = 0xFFFE;
+
+fn placeholder_index(c: char) -> Option<usize> {
+    (c as u32).checked_sub(PLACEHOLDER).map(|i| i as usize).filter(|i| *i < PLACEHOLDERS)
+}
+
+/// Finds the protected regions of a text and keeps them out of reach of cleaning.
+#[derive(Debug, Default)]
+pub struct Protector {
+    protections: Vec<Protection>,
+}
+
+impl Protector {
+    pub fn new(rules: &[Protect]) -> Result<Self> {
+        let mut protections = vec![];
+        for rule in rules {
+            protections.push(match rule {
+                Protect::CodeBlocks => Protection::CodeBlocks,
+                Protect::InlineCode => Protection::InlineCode,
+                Protect::Quotes => Protection::Quotes,
+                Protect::Regex { pattern, case_insensitive, multiline } => Protection::Regex(
+                    ReplaceRule::regex(pattern, "")
+                    .case_insensitive(*case_insensitive)
+                    .multiline(*multiline)
+                    .compile()?
+                ),
+                Protect::Range { start, end } => Protection::Range(*start..*end),
+            });
+        }
+        Ok(Self { protections })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.protections.is_empty()
+    }
+
+    /// The protected regions of `text`, sorted and with overlapping ones merged.
+    pub fn regions(&self, text: &str) -> Vec<Range<usize>> {
+        let mut regions = vec![];
+        for protection in &self.protections {
+            match protection {
+                Protection::CodeBlocks => regions.extend(code_blocks(text)),
+                Protection::InlineCode => regions.extend(INLINE_CODE.find_iter(text).map(|m| m.range())),
+                Protection::Quotes => regions.extend(QUOTES.find_iter(text).map(|m| m.range())),
+                Protection::Regex(rule) => regions.extend(rule.matches(text)),
+                Protection::Range(range) => {
+                    let mut start = range.start.min(text.len());
+                    let mut end = range.end.min(text.len());
+                    while !text.is_char_boundary(start) {
+                        start -= 1;
+                    }
+                    while !text.is_char_boundary(end) {
+                        end += 1;
+                    }
+                    regions.push(start..end);
+                },
+            }
+        }
+        regions.retain(|region| !region.is_empty());
+        regions.sort_by_key(|region| region.start);
+
+        let mut merged: Vec<Range<usize>> = vec![];
+        for region in regions {
+            match merged.last_mut() {
+                Some(last) if region.start <= last.end => last.end = last.end.max(region.end),
+                _ => merged.push(region),
+            }
+        }
+        merged
+    }
+
+    /// Run `clean` over `text` with its protected regions left as they are.
+    ///
+    /// Each region is replaced by a private use character while `clean` runs
+    /// and put back afterwards. If `clean` removes, duplicates or reorders
+    /// these characters (e.g. because it removes symbols), the text between
+    /// the regions is cleaned piece by piece instead.
+    pub fn apply<F>(&self, text: &str, clean: F) -> String
+    where
+        F: Fn(&str) -> String
+    {
+        let regions = self.regions(text);
+        if regions.is_empty() {
+            return clean(text);
+        }
+
+        if regions.len() <= PLACEHOLDERS && !text.chars().any(|c| placeholder_index(c).is_some()) {
+            let mut masked = String::with_capacity(text.len());
+            let mut last = 0;
+            for (i, region) in regions.iter().enumerate() {
+                masked.push_str(&text[last..region.start]);
+                masked.push(char::from_u32(PLACEHOLDER + i as u32).unwrap());
+                last = region.end;
+            }
+            masked.push_str(&text[last..]);
+
+            if let Some(restored) = restore(&clean(&masked), text, &regions) {
+                return restored;
+            }
+        }
+
+        let mut result = String::with_capacity(text.len());
+        let mut last = 0;
+        for region in &regions {
+            if region.start > last {
+                result.push_str(&clean(&text[last..region.start]));
+            }
+            result.push_str(&text[region.clone()]);
+            last = region.end;
+        }
+        if last < text.len() {
+            result.push_str(&clean(&text[last..]));
+        }
+        result
+    }
+}
+
+/// Put the protected regions back in place of their placeholders, as long
+/// as each of them is still there exactly once and in order.
+fn restore(cleaned: &str, text: &str, regions: &[Range<usize>]) -> Option<String> {
+    let mut result = String::with_capacity(cleaned.len());
+    let mut next = 0;
+    for c in cleaned.chars() {
+        match placeholder_index(c) {
+            Some(i) if i == next => {
+                result.push_str(&text[regions[i].clone()]);
+                next += 1;
+            },
+            Some(_) => return None,
+            None => result.push(c),
+        }
+    }
+    (next == regions.len()).then_some(result)
+}
+
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    fn regions<'a>(text: &'a str, rules: &[Protect]) -> Vec<&'a str> {
+        Protector::new(rules).unwrap().regions(text).into_iter().map(|r| &text[r]).collect()
+    }
+
+    #[test]
+    pub fn test_code_blocks() {
+        let text = "It fails:\n```\nlet  x = 1;\n```\nTrace:\nError: boom\n    at main (a.js:1)\n\tat run (b.js:2)\ndone\n~~~\nopen";
+        assert_eq!(
+            regions(text, &[Protect::CodeBlocks]),
+            vec!["```\nlet  x = 1;\n```\n", "    at main (a.js:1)\n\tat run (b.js:2)\n", "~~~\nopen"]
+        );
+    }
+
+    #[test]
+    pub fn test_inline_code_quotes_and_ranges() {
+        let text = "Run `ls  -la` and say \"hi  there\" or «salut» now";
+        let start = text.find("now").unwrap();
+        let rules = [Protect::InlineCode, Protect::Quotes, Protect::Range { start, end: 100 }];
+        assert_eq!(regions(text, &rules), vec!["`ls  -la`", "\"hi  there\"", "«salut»", "now"]);
+    }
+
+    #[test]
+    pub fn test_regex_regions_merge() {
+        let rules = [
+            Protect::Regex { pattern: String::from(r"v\d+(\.\d+)*"), case_insensitive: false, multiline: false },
+            Protect::Range { start: 3, end: 6 },
+        ];
+        assert_eq!(regions("on v1.2.3 and v4", &rules), vec!["v1.2.3", "v4"]);
+    }
+
+    #[test]
+    pub fn test_apply_keeps_regions() {
+        let protector = Protector::new(&[Protect::InlineCode]).unwrap();
+        let squash = |s: &str| s.split_whitespace().collect::<Vec<_>>().join(" ");
+        assert_eq!(protector.apply("  use  `a  b`  here ", squash), "use `a  b` here");
+    }
+
+    #[test]
+    pub fn test_apply_falls_back_when_placeholders_are_removed() {
+        let protector = Protector::new(&[Protect::InlineCode]).unwrap();
+        let ascii_only = |s: &str| s.chars().filter(char::is_ascii).collect::<String>();
+        assert_eq!(protector.apply("café `naïve` olé", ascii_only), "caf `naïve` ol");
+    }
+}
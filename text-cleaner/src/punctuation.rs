@@ -0,0 +1,197 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// A Unicode general category (or group of categories) of
+/// punctuation marks and symbols.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PunctuationCategory {
+    /// All punctuation.
+    P,
+    /// Connector punctuation, e.g. `_`.
+    Pc,
+    /// Dash punctuation, e.g. `-` or `—`.
+    Pd,
+    /// Open punctuation, e.g. `(` or `[`.
+    Ps,
+    /// Close punctuation, e.g. `)` or `]`.
+    Pe,
+    /// Initial quotes, e.g. `«` or `“`.
+    Pi,
+    /// Final quotes, e.g. `»` or `”`.
+    Pf,
+    /// Other punctuation, e.g. `!`, `.` or `'`.
+    Po,
+    /// All symbols.
+    S,
+    /// Math symbols, e.g. `+`.
+    Sm,
+    /// Currency symbols, e.g. `$`.
+    Sc,
+    /// Modifier symbols, e.g. `^`.
+    Sk,
+    /// Other symbols, e.g. `©`.
+    So,
+}
+
+impl PunctuationCategory {
+    fn as_str(&self) -> &'static str {
+        match self {
+            PunctuationCategory::P => "P",
+            PunctuationCategory::Pc => "Pc",
+            PunctuationCategory::Pd => "Pd",
+            PunctuationCategory::Ps => "Ps",
+            PunctuationCategory::Pe => "Pe",
+            PunctuationCategory::Pi => "Pi",
+            PunctuationCategory::Pf => "Pf",
+            PunctuationCategory::Po => "Po",
+            PunctuationCategory::S => "S",
+            PunctuationCategory::Sm => "Sm",
+            PunctuationCategory::Sc => "Sc",
+            PunctuationCategory::Sk => "Sk",
+            PunctuationCategory::So => "So",
+        }
+    }
+}
+
+/// Options for removing punctuation marks.
+///
+/// The defaults remove every character that is neither a word
+/// character nor whitespace, just like `Clean::remove_punctuation_marks`.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PunctuationOptions {
+    /// Keep apostrophes between two letters, e.g. "don't".
+    pub keep_intraword_apostrophes: bool,
+    /// Keep hyphens between two letters or digits, e.g. "well-known".
+    pub keep_intraword_hyphens: bool,
+    /// Keep punctuation between two digits, e.g. "3.14" or "1,000".
+    pub keep_numeric: bool,
+    /// Characters that are never removed.
+    pub allow: String,
+    /// Replace every run of removed characters by a single space instead of deleting it.
+    pub replace_with_space: bool,
+    /// Only remove characters from these categories. When unset, everything
+    /// that is neither a word character nor whitespace is removed.
+    pub categories: Option<Vec<PunctuationCategory>>,
+}
+
+const APOSTROPHES: &[char] = &['\'', '\u{2019}', '\u{02BC}'];
+const HYPHENS: &[char] = &['-', '\u{2010}', '\u{2011}'];
+
+/// Removes punctuation marks according to a set of [`PunctuationOptions`].
+#[derive(Debug, Clone)]
+pub struct PunctuationFilter {
+    re: Regex,
+    options: PunctuationOptions,
+}
+
+impl PunctuationFilter {
+    pub fn new(options: &PunctuationOptions) -> Self {
+        let pattern = match &options.categories {
+            None => String::from(r"[^\w\s]"),
+            Some(categories) if categories.is_empty() => String::from(r"[^\s\S]"),
+            Some(categories) => format!(
+                "[{}]",
+                categories
+                    .iter()
+                    .map(|category| format!(r"\p{{{}}}", category.as_str()))
+                    .collect::<String>()
+            ),
+        };
+        Self {
+            re: Regex::new(&pattern).expect("Punctuation patterns are always valid."),
+            options: options.clone(),
+        }
+    }
+
+    fn keep(&self, c: char, before: Option<char>, after: Option<char>) -> bool {
+        let options = &self.options;
+        let between = |f: fn(char) -> bool| before.is_some_and(f) && after.is_some_and(f);
+
+        options.allow.contains(c)
+        || (options.keep_intraword_apostrophes && APOSTROPHES.contains(&c) && between(char::is_alphabetic))
+        || (options.keep_intraword_hyphens && HYPHENS.contains(&c) && between(char::is_alphanumeric))
+        || (options.keep_numeric && between(|c| c.is_ascii_digit()))
+    }
+
+    /// Remove the grapheme clusters whose first character is a targeted
+    /// punctuation mark, along with any marks attached to it.
+    pub fn apply(&self, text: &str) -> String {
+        let mut result = String::with_capacity(text.len());
+        let mut removed_previous = false;
+        let mut before = None;
+        let mut clusters = text.graphemes(true).peekable();
+
+        while let Some(cluster) = clusters.next() {
+            let c = cluster.chars().next().unwrap();
+            let after = clusters.peek().and_then(|next| next.chars().next());
+            let targeted = self.re.is_match(&cluster[..c.len_utf8()]);
+
+            if targeted && !self.keep(c, before, after) {
+                if self.options.replace_with_space && !removed_previous {
+                    result.push(' ');
+                }
+                removed_previous = true;
+            } else {
+                result.push_str(cluster);
+                removed_previous = false;
+            }
+            before = cluster.chars().next_back();
+        }
+        result
+    }
+}
+
+/// Remove punctuation marks from `text` according to `options`.
+pub fn remove_punctuation(text: &str, options: &PunctuationOptions) -> String {
+    PunctuationFilter::new(options).apply(text)
+}
+
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_default_matches_remove_punctuation_marks() {
+        let s = "!hi. wh?at is the weat[h]er lik?e.";
+        assert_eq!(remove_punctuation(s, &PunctuationOptions::default()), "hi what is the weather like");
+    }
+
+    #[test]
+    pub fn test_keep_meaningful_characters() {
+        let options = PunctuationOptions {
+            keep_intraword_apostrophes: true,
+            keep_intraword_hyphens: true,
+            keep_numeric: true,
+            allow: String::from("$"),
+            ..Default::default()
+        };
+        assert_eq!(
+            remove_punctuation("Don't pay $3.14 for a well-known 'thing' - ever!", &options),
+            "Don't pay $3.14 for a well-known thing  ever"
+        );
+    }
+
+    #[test]
+    pub fn test_replace_with_space() {
+        let options = PunctuationOptions { replace_with_space: true, ..Default::default() };
+        assert_eq!(remove_punctuation("a...b,c", &options), "a b c");
+    }
+
+    #[test]
+    pub fn test_categories() {
+        let options = PunctuationOptions {
+            categories: Some(vec![PunctuationCategory::Ps, PunctuationCategory::Pe]),
+            ..Default::default()
+        };
+        assert_eq!(remove_punctuation("(a) [b], {c}!", &options), "a b, c!");
+    }
+
+    #[test]
+    pub fn test_attached_marks_go_with_their_base() {
+        let options = PunctuationOptions::default();
+        assert_eq!(remove_punctuation("a?\u{0301}b cafe\u{0301}", &options), "ab cafe\u{0301}");
+    }
+}
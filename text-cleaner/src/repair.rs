@@ -0,0 +1,219 @@
+use std::path::PathBuf;
+
+use hashbrown::HashSet;
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+use crate::wrap::unwrap_text_keeping;
+
+/// Options for repairing text extracted from PDFs or OCR.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RepairOptions {
+    /// Expand ligatures like `ﬁ` into their letters.
+    pub expand_ligatures: bool,
+    /// Remove soft hyphens.
+    pub remove_soft_hyphens: bool,
+    /// Join words that are hyphenated across a line break.
+    pub dehyphenate: bool,
+    /// Join the lines of each paragraph into a single line.
+    pub unwrap_lines: bool,
+    /// A file with one word per line, used along with the words of the text
+    /// itself and `words` to tell real hyphens from line-break hyphens.
+    pub word_list: Option<PathBuf>,
+    pub words: Vec<String>,
+}
+
+impl Default for RepairOptions {
+    fn default() -> Self {
+        Self {
+            expand_ligatures: true,
+            remove_soft_hyphens: true,
+            dehyphenate: true,
+            unwrap_lines: true,
+            word_list: None,
+            words: vec![],
+        }
+    }
+}
+
+lazy_static! {
+    static ref WORD: Regex = Regex::new(r"\w+(?:[-‐]\w+)*").unwrap();
+    /// A word broken across a line break with a hyphen. There must be a
+    /// letter on either side of the hyphen, so that ranges like "1990-2000" are left alone.
+    static ref HYPHENATED: Regex = Regex::new(r"(\w*\p{L})[-‐\u{AD}][^\S\n]*\n[^\S\n]*(\p{L}\w*(?:[-‐']\w+)*)(\S*)[^\S\n]*").unwrap();
+}
+
+fn expand_ligature(c: char) -> Option<&'static str> {
+    let letters = match c {
+        'ﬀ' => "ff",
+        'ﬁ' => "fi",
+        'ﬂ' => "fl",
+        'ﬃ' => "ffi",
+        'ﬄ' => "ffl",
+        'ﬅ' | 'ﬆ' => "st",
+        'Ĳ' => "IJ",
+        'ĳ' => "ij",
+        _ => return None,
+    };
+    Some(letters)
+}
+
+pub fn expand_ligatures(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        match expand_ligature(c) {
+            Some(letters) => result.push_str(letters),
+            None => result.push(c),
+        }
+    }
+    result
+}
+
+/// Whether a line looks like a heading: Markdown-style, or short, in
+/// capitals or without closing punctuation, and followed by a capital.
+fn is_heading(line: &str, next: &str, width: usize) -> bool {
+    let line = line.trim();
+    let short = line.chars().count() * 5 < width * 3;
+    let capitals = line.chars().any(char::is_alphabetic) && !line.chars().any(char::is_lowercase);
+    let closed = line.ends_with(|c: char| ".,;:!?)\"'”’".contains(c));
+    line.starts_with('#')
+        || (short && capitals)
+        || (short && !closed && next.trim_start().starts_with(char::is_uppercase))
+}
+
+/// Repairs the line breaks, hyphens and ligatures of extracted text.
+#[derive(Debug, Clone)]
+pub struct Repairer {
+    options: RepairOptions,
+    words: HashSet<String>,
+}
+
+impl Repairer {
+    pub fn new(options: &RepairOptions) -> Result<Self> {
+        let mut words = options.words.iter().map(|word| word.to_lowercase()).collect::<HashSet<_>>();
+        if let Some(path) = &options.word_list {
+            let contents = std::fs::read_to_string(path)?;
+            words.extend(contents.lines().map(|word| word.trim().to_lowercase()).filter(|word| !word.is_empty()));
+        }
+        Ok(Self { options: options.clone(), words })
+    }
+
+    /// Join the halves of words hyphenated across a line break. The hyphen
+    /// is kept if the hyphenated spelling is known, or if the joined one isn't
+    /// and the halves are words of their own (or the second one is capitalized,
+    /// as in names). The rest of the second line stays on the next line.
+    fn dehyphenate(&self, text: &str) -> String {
+        let sites = HYPHENATED.find_iter(text).map(|m| m.range()).collect::<Vec<_>>();
+        let mut vocabulary = WORD
+            .find_iter(text)
+            .filter(|word| !sites.iter().any(|site| site.start < word.end() && word.start() < site.end))
+            .map(|word| word.as_str().to_lowercase())
+            .collect::<HashSet<_>>();
+        vocabulary.extend(self.words.iter().cloned());
+        let known = |word: &str| vocabulary.contains(&word.to_lowercase());
+
+        HYPHENATED.replace_all(text, |captures: &Captures| {
+            let whole = captures.get(0).unwrap();
+            let (head, tail, rest) = (&captures[1], &captures[2], &captures[3]);
+            let joined = format!("{}{}", head, tail);
+            let hyphenated = format!("{}-{}", head, tail);
+            let soft = whole.as_str()[head.len()..].starts_with('\u{AD}');
+            let keep_hyphen = !soft
+                && (known(&hyphenated)
+                    || (!known(&joined) && (tail.starts_with(char::is_uppercase) || (known(head) && known(tail)))));
+            let word = if keep_hyphen { hyphenated } else { joined };
+            match text[whole.end()..].starts_with('\n') || whole.end() == text.len() {
+                true => format!("{}{}", word, rest),
+                false => format!("{}{}\n", word, rest),
+            }
+        }).into_owned()
+    }
+
+    /// Join the lines of each paragraph like [`crate::wrap::unwrap_text`],
+    /// also keeping the breaks after headings and after lines that end a
+    /// paragraph early.
+    fn unwrap_lines(&self, text: &str) -> String {
+        let width = text.split('\n').map(|line| line.trim_end().chars().count()).max().unwrap_or(0);
+        unwrap_text_keeping(text, |line, next| {
+            let trimmed = line.trim_end();
+            let ends_early = trimmed.ends_with(['.', '!', '?', ':']) && trimmed.chars().count() * 5 < width * 4;
+            ends_early || is_heading(line, next, width)
+        })
+    }
+
+    pub fn apply(&self, text: &str) -> String {
+        let mut text = text.replace("\r\n", "\n");
+        if self.options.expand_ligatures {
+            text = expand_ligatures(&text);
+        }
+        if self.options.dehyphenate {
+            text = self.dehyphenate(&text);
+        }
+        if self.options.remove_soft_hyphens {
+            text = text.replace('\u{AD}', "");
+        }
+        if self.options.unwrap_lines {
+            text = self.unwrap_lines(&text);
+        }
+        text
+    }
+}
+
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    fn repair(text: &str, options: RepairOptions) -> String {
+        Repairer::new(&options).unwrap().apply(text)
+    }
+
+    fn only(dehyphenate: bool, unwrap_lines: bool) -> RepairOptions {
+        RepairOptions { dehyphenate, unwrap_lines, ..Default::default() }
+    }
+
+    #[test]
+    pub fn test_expand_ligatures() {
+        assert_eq!(expand_ligatures("ﬁnal ﬂow, aﬀord oﬃce"), "final flow, afford office");
+    }
+
+    #[test]
+    pub fn test_dehyphenate() {
+        let text = "The experi-\nment was well-\nknown in self-\nservice stores. A self-service\nstore near Jean-\nPaul's house, mar\u{AD}\nket.";
+        assert_eq!(
+            repair(text, only(true, false)),
+            "The experiment\nwas wellknown\nin self-service\nstores. A self-service\nstore near Jean-Paul's\nhouse, market."
+        );
+        assert_eq!(repair("from 1990-\n2000 on, page 12-\n14", only(true, false)), "from 1990-\n2000 on, page 12-\n14");
+        assert_eq!(repair("model A4-\n5 and B-\n52", only(true, false)), "model A4-\n5 and B-\n52");
+    }
+
+    #[test]
+    pub fn test_word_list_decides_hyphens() {
+        let options = RepairOptions { words: vec![String::from("well-known")], ..only(true, false) };
+        assert_eq!(repair("a well-\nknown fact", options), "a well-known\nfact");
+        assert_eq!(repair("a well-\nknown fact, known well", only(true, false)), "a well-known\nfact, known well");
+        assert_eq!(repair("a well-\nknown fact", only(true, false)), "a wellknown\nfact");
+    }
+
+    #[test]
+    pub fn test_unwrap_lines() {
+        let text = "INTRODUCTION\nThis paragraph was wrapped by the PDF\nextractor in the middle of a sentence\nand goes on here.\n\nShort line.\nNext paragraph starts\nhere, and has a list:\n- first item that\n  wraps around\n- second item\n1. numbered";
+        assert_eq!(
+            repair(text, only(false, true)),
+            "INTRODUCTION\nThis paragraph was wrapped by the PDF extractor in the middle of a sentence and goes on here.\n\nShort line.\nNext paragraph starts here, and has a list:\n- first item that wraps around\n- second item\n1. numbered"
+        );
+    }
+
+    #[test]
+    pub fn test_repair() {
+        let text = "The ﬁrst experi-\nment of the oﬃce was a suc-\ncess in every respect, and we were\nvery happy with it.";
+        assert_eq!(
+            repair(text, RepairOptions::default()),
+            "The first experiment of the office was a success in every respect, and we were very happy with it."
+        );
+    }
+}
@@ -0,0 +1,166 @@
+use std::ops::Range;
+
+use regex::{NoExpand, Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+
+/// The longest pattern (in bytes) a user may supply.
+pub const MAX_PATTERN_LENGTH: usize = 1024;
+
+/// The maximum size (in bytes) of a compiled user-supplied regex.
+pub const REGEX_SIZE_LIMIT: usize = 1 << 20;
+
+/// The maximum size (in bytes) of the lazy DFA cache of a user-supplied regex.
+pub const REGEX_DFA_SIZE_LIMIT: usize = 1 << 21;
+
+/// The deepest nesting of groups and repetitions a user-supplied regex may have.
+pub const REGEX_NEST_LIMIT: u32 = 64;
+
+/// A user-defined find/replace rule.
+///
+/// When `regex` is set, `pattern` is a regular expression and `replacement`
+/// may refer to capture groups with `$1` or `${name}`. Otherwise both are
+/// taken literally.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct ReplaceRule {
+    pub pattern: String,
+    #[serde(default)]
+    pub replacement: String,
+    #[serde(default)]
+    pub regex: bool,
+    #[serde(default)]
+    pub case_insensitive: bool,
+    #[serde(default)]
+    pub multiline: bool,
+}
+
+impl ReplaceRule {
+    pub fn literal(pattern: &str, replacement: &str) -> Self {
+        Self {
+            pattern: pattern.to_owned(),
+            replacement: replacement.to_owned(),
+            ..Default::default()
+        }
+    }
+
+    pub fn regex(pattern: &str, replacement: &str) -> Self {
+        Self {
+            regex: true,
+            ..Self::literal(pattern, replacement)
+        }
+    }
+
+    pub fn case_insensitive(mut self, yes: bool) -> Self {
+        self.case_insensitive = yes;
+        self
+    }
+
+    pub fn multiline(mut self, yes: bool) -> Self {
+        self.multiline = yes;
+        self
+    }
+
+    /// Compile the rule, enforcing the size and complexity limits
+    /// on the pattern. An empty pattern is rejected, since it would match
+    /// between every character.
+    pub fn compile(&self) -> Result<CompiledRule> {
+        if self.pattern.is_empty() {
+            return Err(Error::EmptyPattern);
+        }
+        if self.pattern.len() > MAX_PATTERN_LENGTH {
+            return Err(Error::PatternTooLong {
+                length: self.pattern.len(),
+                limit: MAX_PATTERN_LENGTH,
+            });
+        }
+        let pattern = match self.regex {
+            true => self.pattern.clone(),
+            false => regex::escape(&self.pattern),
+        };
+        let re = RegexBuilder::new(&pattern)
+            .case_insensitive(self.case_insensitive)
+            .multi_line(self.multiline)
+            .size_limit(REGEX_SIZE_LIMIT)
+            .dfa_size_limit(REGEX_DFA_SIZE_LIMIT)
+            .nest_limit(REGEX_NEST_LIMIT)
+            .build()?;
+
+        Ok(CompiledRule {
+            re,
+            replacement: self.replacement.clone(),
+            expand: self.regex,
+        })
+    }
+}
+
+/// A [`ReplaceRule`] that is ready to be applied.
+#[derive(Debug, Clone)]
+pub struct CompiledRule {
+    re: Regex,
+    replacement: String,
+    expand: bool,
+}
+
+impl CompiledRule {
+    pub fn apply(&self, text: &str) -> String {
+        match self.expand {
+            true => self.re.replace_all(text, self.replacement.as_str()).into_owned(),
+            false => self.re.replace_all(text, NoExpand(&self.replacement)).into_owned(),
+        }
+    }
+
+    /// The byte ranges of `text` that the rule would replace.
+    pub fn matches(&self, text: &str) -> Vec<Range<usize>> {
+        self.re.find_iter(text).map(|m| m.range()).collect()
+    }
+}
+
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_literal_rule() {
+        let rule = ReplaceRule::literal("[deleted]", "").compile().unwrap();
+        assert_eq!(rule.apply("a [deleted] b [deleted]"), "a  b ");
+    }
+
+    #[test]
+    pub fn test_literal_rule_does_not_expand_replacement() {
+        let rule = ReplaceRule::literal("price", "$1").compile().unwrap();
+        assert_eq!(rule.apply("the price"), "the $1");
+    }
+
+    #[test]
+    pub fn test_regex_rule_with_capture_groups() {
+        let rule = ReplaceRule::regex(r"Fig\. (\d+)", "Figure $1")
+            .case_insensitive(true)
+            .compile()
+            .unwrap();
+        assert_eq!(rule.apply("See fig. 3 and Fig. 12."), "See Figure 3 and Figure 12.");
+        assert_eq!(rule.matches("See fig. 3"), vec![4..10]);
+    }
+
+    #[test]
+    pub fn test_multiline_rule() {
+        let rule = ReplaceRule::regex(r"^> ", "").multiline(true).compile().unwrap();
+        assert_eq!(rule.apply("> a\n> b"), "a\nb");
+    }
+
+    #[test]
+    pub fn test_rule_limits() {
+        let long = "a".repeat(MAX_PATTERN_LENGTH + 1);
+        assert!(matches!(
+            ReplaceRule::literal(&long, "").compile(),
+            Err(Error::PatternTooLong { .. })
+        ));
+        assert!(matches!(
+            ReplaceRule::regex(r"\w{1000}{1000}", "").compile(),
+            Err(Error::InvalidPattern(_))
+        ));
+        assert!(matches!(ReplaceRule::literal("", "x").compile(), Err(Error::EmptyPattern)));
+        assert!(matches!(ReplaceRule::regex("", "x").compile(), Err(Error::EmptyPattern)));
+    }
+}
@@ -0,0 +1,198 @@
+use std::ops::Range;
+
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::error::Result;
+use crate::replace::{CompiledRule, ReplaceRule};
+
+/// The part of a text that a scoped step applies to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Scope {
+    /// The lines that contain a match of `pattern` (or that don't, if `invert` is set).
+    LinesMatching {
+        pattern: String,
+        #[serde(default)]
+        case_insensitive: bool,
+        #[serde(default)]
+        invert: bool,
+    },
+    /// The first `count` lines.
+    FirstLines { count: usize },
+    /// The last `count` lines.
+    LastLines { count: usize },
+    /// Lines `from` through `to`, counting from 1. Without `to`, up to the last line.
+    LineRange {
+        from: usize,
+        #[serde(default)]
+        to: Option<usize>,
+    },
+    /// The paragraphs (separated by blank lines) that meet every given condition.
+    Paragraphs {
+        /// The fewest characters a paragraph must have.
+        #[serde(default)]
+        min_length: Option<usize>,
+        /// The most characters a paragraph may have.
+        #[serde(default)]
+        max_length: Option<usize>,
+        /// A pattern a paragraph must contain a match of.
+        #[serde(default)]
+        pattern: Option<String>,
+    },
+}
+
+lazy_static! {
+    static ref BLANK_LINES: Regex = Regex::new(r"\n[^\S\n]*\n\s*").unwrap();
+}
+
+fn compile_pattern(pattern: &str, case_insensitive: bool) -> Result<CompiledRule> {
+    ReplaceRule::regex(pattern, "").case_insensitive(case_insensitive).compile()
+}
+
+/// A [`Scope`] that is ready to be applied.
+#[derive(Debug)]
+pub enum CompiledScope {
+    LinesMatching { rule: CompiledRule, invert: bool },
+    FirstLines(usize),
+    LastLines(usize),
+    LineRange(usize, Option<usize>),
+    Paragraphs { min_length: Option<usize>, max_length: Option<usize>, rule: Option<CompiledRule> },
+}
+
+impl Scope {
+    pub fn compile(&self) -> Result<CompiledScope> {
+        Ok(match self {
+            Scope::LinesMatching { pattern, case_insensitive, invert } => CompiledScope::LinesMatching {
+                rule: compile_pattern(pattern, *case_insensitive)?,
+                invert: *invert,
+            },
+            Scope::FirstLines { count } => CompiledScope::FirstLines(*count),
+            Scope::LastLines { count } => CompiledScope::LastLines(*count),
+            Scope::LineRange { from, to } => CompiledScope::LineRange(*from, *to),
+            Scope::Paragraphs { min_length, max_length, pattern } => CompiledScope::Paragraphs {
+                min_length: *min_length,
+                max_length: *max_length,
+                rule: pattern.as_deref().map(|pattern| compile_pattern(pattern, false)).transpose()?,
+            },
+        })
+    }
+}
+
+/// The byte ranges of the paragraphs of `text`, without the blank lines between them.
+pub(crate) fn paragraphs(text: &str) -> Vec<Range<usize>> {
+    let mut paragraphs = vec![];
+    let mut start = 0;
+    for separator in BLANK_LINES.find_iter(text) {
+        paragraphs.push(start..separator.start());
+        start = separator.end();
+    }
+    paragraphs.push(start..text.len());
+    paragraphs
+}
+
+impl CompiledScope {
+    /// Whether the line at `index` (counting from 0) out of `count` lines is in scope.
+    fn contains_line(&self, line: &str, index: usize, count: usize) -> bool {
+        match self {
+            CompiledScope::LinesMatching { rule, invert } => rule.matches(line).is_empty() == *invert,
+            CompiledScope::FirstLines(n) => index < *n,
+            CompiledScope::LastLines(n) => index + n >= count,
+            CompiledScope::LineRange(from, to) => index + 1 >= *from && to.is_none_or(|to| index < to),
+            CompiledScope::Paragraphs { .. } => unreachable!("paragraphs are not scoped by line"),
+        }
+    }
+
+    fn contains_paragraph(&self, paragraph: &str) -> bool {
+        match self {
+            CompiledScope::Paragraphs { min_length, max_length, rule } => {
+                let length = paragraph.chars().count();
+                min_length.is_none_or(|min| length >= min)
+                    && max_length.is_none_or(|max| length <= max)
+                    && rule.as_ref().is_none_or(|rule| !rule.matches(paragraph).is_empty())
+            },
+            _ => unreachable!("lines are not scoped by paragraph"),
+        }
+    }
+
+    /// Run `clean` over the parts of `text` in scope, leaving the rest as it is.
+    /// Lines are cleaned without their line breaks.
+    pub fn apply<F>(&self, text: &str, clean: F) -> String
+    where
+        F: Fn(&str) -> String
+    {
+        let mut result = String::with_capacity(text.len());
+        if let CompiledScope::Paragraphs { .. } = self {
+            let mut last = 0;
+            for paragraph in paragraphs(text) {
+                result.push_str(&text[last..paragraph.start]);
+                let content = &text[paragraph.clone()];
+                match self.contains_paragraph(content) {
+                    true => result.push_str(&clean(content)),
+                    false => result.push_str(content),
+                }
+                last = paragraph.end;
+            }
+            result.push_str(&text[last..]);
+            return result;
+        }
+
+        // A final line break ends the last line rather than starting an empty one.
+        let body = text.strip_suffix('\n').unwrap_or(text);
+        let count = body.split('\n').count();
+        for (index, line) in body.split('\n').enumerate() {
+            if index > 0 {
+                result.push('\n');
+            }
+            match self.contains_line(line, index, count) {
+                true => result.push_str(&clean(line)),
+                false => result.push_str(line),
+            }
+        }
+        if body.len() < text.len() {
+            result.push('\n');
+        }
+        result
+    }
+}
+
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    fn upper(scope: Scope, text: &str) -> String {
+        scope.compile().unwrap().apply(text, |s| s.to_uppercase())
+    }
+
+    #[test]
+    pub fn test_lines_matching() {
+        let scope = Scope::LinesMatching { pattern: String::from("^>"), case_insensitive: false, invert: false };
+        assert_eq!(upper(scope, "> quoted\nreply\n> again"), "> QUOTED\nreply\n> AGAIN");
+
+        let scope = Scope::LinesMatching { pattern: String::from("^>"), case_insensitive: false, invert: true };
+        assert_eq!(upper(scope, "> quoted\nreply\n> again"), "> quoted\nREPLY\n> again");
+    }
+
+    #[test]
+    pub fn test_line_positions() {
+        let text = "a\nb\nc\nd";
+        assert_eq!(upper(Scope::FirstLines { count: 1 }, text), "A\nb\nc\nd");
+        assert_eq!(upper(Scope::LastLines { count: 2 }, text), "a\nb\nC\nD");
+        assert_eq!(upper(Scope::LineRange { from: 2, to: Some(3) }, text), "a\nB\nC\nd");
+        assert_eq!(upper(Scope::LineRange { from: 3, to: None }, text), "a\nb\nC\nD");
+        assert_eq!(upper(Scope::LastLines { count: 1 }, "title\nlast line\n"), "title\nLAST LINE\n");
+        assert_eq!(upper(Scope::LastLines { count: 1 }, "title\n\n"), "title\n\n");
+    }
+
+    #[test]
+    pub fn test_paragraphs() {
+        let text = "short\n\nthis one is longer\nand spans lines\n  \n\nend";
+        let scope = Scope::Paragraphs { min_length: Some(10), max_length: None, pattern: None };
+        assert_eq!(upper(scope, text), "short\n\nTHIS ONE IS LONGER\nAND SPANS LINES\n  \n\nend");
+
+        let scope = Scope::Paragraphs { min_length: None, max_length: Some(5), pattern: Some(String::from("^e")) };
+        assert_eq!(upper(scope, text), "short\n\nthis one is longer\nand spans lines\n  \n\nEND");
+    }
+}
@@ -0,0 +1,160 @@
+use serde::{Deserialize, Serialize};
+use unicode_script::{Script, ScriptExtension, UnicodeScript};
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::error::{Error, Result};
+
+/// Whether the listed scripts are the ones to keep or the ones to remove.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScriptMode {
+    #[default]
+    Keep,
+    Remove,
+}
+
+/// What to do with grapheme clusters that don't belong to any particular
+/// script, like spaces, digits, most punctuation and emojis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CommonHandling {
+    /// Always keep them.
+    #[default]
+    Keep,
+    /// Always remove them.
+    Remove,
+    /// Keep them if the closest preceding script-specific cluster was kept.
+    Follow,
+}
+
+/// Options for keeping or removing text by its Unicode Script property.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScriptOptions {
+    pub mode: ScriptMode,
+    /// Full (`"Cyrillic"`) or short (`"Cyrl"`) script names.
+    pub scripts: Vec<String>,
+    pub common: CommonHandling,
+}
+
+/// Keeps or removes extended grapheme clusters by script.
+///
+/// A cluster belongs to the scripts shared by all of its characters, so a base
+/// letter followed by combining marks (which are `Inherited`) belongs to the
+/// script of the letter. Characters used by several scripts, like the
+/// ideographic full stop, match any of them (per `Script_Extensions`).
+#[derive(Debug, Clone)]
+pub struct ScriptFilter {
+    mode: ScriptMode,
+    scripts: Vec<Script>,
+    common: CommonHandling,
+}
+
+pub fn parse_script(name: &str) -> Result<Script> {
+    Script::from_full_name(name)
+        .or_else(|| Script::from_short_name(name))
+        .ok_or_else(|| Error::UnknownScript(name.to_owned()))
+}
+
+/// Whether a cluster belongs to no script in particular (`Common` or `Inherited`).
+pub(crate) fn is_neutral(ext: ScriptExtension) -> bool {
+    ext.is_common() || ext.is_inherited()
+}
+
+/// The scripts shared by all the characters of a grapheme cluster.
+pub(crate) fn cluster_extension(cluster: &str) -> ScriptExtension {
+    let ext = ScriptExtension::for_str(cluster);
+    match ext.is_empty() {
+        // A cluster mixing unrelated scripts; go by its first character.
+        true => cluster.chars().next().map(|c| c.script_extension()).unwrap_or(ext),
+        false => ext,
+    }
+}
+
+impl ScriptFilter {
+    pub fn new(options: &ScriptOptions) -> Result<Self> {
+        Ok(Self {
+            mode: options.mode,
+            scripts: options.scripts.iter().map(|name| parse_script(name)).collect::<Result<_>>()?,
+            common: options.common,
+        })
+    }
+
+    fn keep_script(&self, ext: ScriptExtension) -> bool {
+        let listed = self.scripts.iter().any(|script| ext.contains_script(*script));
+        match self.mode {
+            ScriptMode::Keep => listed,
+            ScriptMode::Remove => !listed,
+        }
+    }
+
+    pub fn apply(&self, text: &str) -> String {
+        let mut result = String::with_capacity(text.len());
+        let mut kept_previous = true;
+
+        for cluster in text.graphemes(true) {
+            let ext = cluster_extension(cluster);
+            let keep = match is_neutral(ext) {
+                true => match self.common {
+                    CommonHandling::Keep => true,
+                    CommonHandling::Remove => false,
+                    CommonHandling::Follow => kept_previous,
+                },
+                false => {
+                    kept_previous = self.keep_script(ext);
+                    kept_previous
+                }
+            };
+            if keep {
+                result.push_str(cluster);
+            }
+        }
+        result
+    }
+}
+
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    fn apply(text: &str, mode: ScriptMode, scripts: &[&str], common: CommonHandling) -> String {
+        let options = ScriptOptions {
+            mode,
+            scripts: scripts.iter().map(|s| s.to_string()).collect(),
+            common,
+        };
+        ScriptFilter::new(&options).unwrap().apply(text)
+    }
+
+    #[test]
+    pub fn test_keep_scripts() {
+        assert_eq!(
+            apply("Hello, Привет! Γειά 123", ScriptMode::Keep, &["Latin", "Cyrl"], CommonHandling::Keep),
+            "Hello, Привет!  123"
+        );
+    }
+
+    #[test]
+    pub fn test_remove_scripts_follow_common() {
+        assert_eq!(
+            apply("index 索引。 page", ScriptMode::Remove, &["Han"], CommonHandling::Follow),
+            "index page"
+        );
+    }
+
+    #[test]
+    pub fn test_combining_marks_stay_with_their_base() {
+        // "e" followed by a combining acute accent, then a Greek letter with a combining mark.
+        assert_eq!(
+            apply("e\u{0301}\u{03B1}\u{0301}", ScriptMode::Keep, &["Greek"], CommonHandling::Keep),
+            "\u{03B1}\u{0301}"
+        );
+    }
+
+    #[test]
+    pub fn test_unknown_script() {
+        let options = ScriptOptions { scripts: vec![String::from("Elvish")], ..Default::default() };
+        assert!(matches!(ScriptFilter::new(&options), Err(Error::UnknownScript(_))));
+    }
+}
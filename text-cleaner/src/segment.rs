@@ -0,0 +1,309 @@
+use hashbrown::HashSet;
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::language::Language;
+
+/// Common abbreviations that don't end a sentence, in lowercase and without
+/// their final period. Initials are recognized apart from these (see [`is_initial`]).
+fn abbreviations(language: Language) -> &'static [&'static str] {
+    match language {
+        Language::English => &[
+            "mr", "mrs", "ms", "dr", "prof", "sr", "jr", "st", "mt", "ave", "vs", "e.g", "i.e", "cf", "al", "approx",
+            "dept", "est", "fig", "figs", "inc", "ltd", "co", "corp", "no", "nos", "vol", "pp", "ed", "eds", "u.s",
+            "u.k", "jan", "feb", "mar", "apr", "jun", "jul", "aug", "sep", "sept", "oct", "nov", "dec", "gen", "gov",
+            "sen", "rep", "rev", "capt", "col", "lt", "sgt",
+        ],
+        Language::German => &[
+            "z.b", "d.h", "u.a", "bzw", "ca", "nr", "str", "dr", "prof", "hr", "fr", "vgl", "evtl", "ggf", "inkl",
+            "bzgl", "ebd", "geb", "gest", "jh", "mio", "mrd", "s", "sog", "u.ä", "z.t", "abs", "art", "bd",
+        ],
+        Language::French => &[
+            "m", "mm", "mme", "mmes", "mlle", "dr", "pr", "p.ex", "cf", "av", "bd", "env", "chap", "éd", "fig",
+            "janv", "févr", "avr", "juil", "sept", "oct", "nov", "déc", "st", "ste", "vol", "n.b",
+        ],
+        Language::Spanish => &[
+            "sr", "sra", "srta", "dr", "dra", "ud", "uds", "p.ej", "pág", "págs", "núm", "av", "avda", "aprox",
+            "cap", "dto", "ej", "lic", "ing", "prof", "vol", "ene", "feb", "abr", "ago", "sept", "oct", "nov", "dic",
+        ],
+        Language::Italian => &[
+            "sig", "sigg", "sig.ra", "dott", "dott.ssa", "prof", "ing", "avv", "arch", "geom", "p.es", "ecc", "pag",
+            "pagg", "cap", "vol", "n", "nr", "ca", "gen", "feb", "mar", "apr", "giu", "lug", "ago", "set", "ott",
+            "nov", "dic",
+        ],
+        Language::Portuguese => &[
+            "sr", "sra", "srta", "dr", "dra", "prof", "profa", "eng", "av", "p.ex", "pág", "págs", "cap", "vol",
+            "n", "nº", "jan", "fev", "mar", "abr", "mai", "jun", "jul", "ago", "set", "out", "nov", "dez",
+        ],
+        Language::Dutch => &[
+            "dhr", "mevr", "mw", "dr", "prof", "ir", "ing", "mr", "drs", "bijv", "bv", "d.w.z", "o.a", "m.b.t",
+            "i.p.v", "ca", "nr", "blz", "vgl", "jl", "jan", "feb", "mrt", "apr", "jun", "jul", "aug", "sep", "okt",
+            "nov", "dec",
+        ],
+        Language::Russian => &[
+            "т.е", "т.к", "т.д", "т.п", "и.о", "г", "гг", "в", "вв", "стр", "с", "см", "рис", "табл", "им", "ул",
+            "пр", "д", "кв", "акад", "проф", "доц", "млн", "млрд", "тыс", "руб", "коп",
+        ],
+    }
+}
+
+lazy_static! {
+    static ref BLANK_LINES: Regex = Regex::new(r"\n[^\S\n]*\n").unwrap();
+}
+
+/// Whether a single capital letter between `before` and `after`, followed by
+/// a period, is an initial: part of a run like "J. R. R.", or between two
+/// capitalized words as in "John F. Kennedy". Otherwise it may well end a
+/// sentence, as in "We chose plan B."
+fn is_initial(before: &str, after: &str) -> bool {
+    let is_initial = |word: &str| {
+        let mut chars = word.chars();
+        matches!((chars.next(), chars.next(), chars.next()), (Some(c), Some('.'), None) if c.is_uppercase())
+    };
+    let is_name = |word: &str| word.starts_with(char::is_uppercase) && word.chars().nth(1).is_some_and(char::is_lowercase);
+    let previous = before.split_whitespace().next_back().unwrap_or("");
+    let next = after.split_whitespace().next().unwrap_or("");
+    is_initial(previous) || is_initial(next) || (is_name(previous) && is_name(next))
+}
+
+/// How segmented text is written out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SegmentOutput {
+    /// One sentence per line, with the whitespace inside each collapsed.
+    #[default]
+    SentencePerLine,
+    /// The words, separated by single spaces.
+    Tokens,
+    /// A JSON list of the words, with their offsets.
+    TokensJson,
+}
+
+/// Options for splitting text into sentences and words.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SegmentOptions {
+    /// Chooses the built-in abbreviations.
+    pub language: Language,
+    /// More abbreviations that don't end a sentence, like `"approx."`.
+    pub abbreviations: Vec<String>,
+    /// Keep punctuation marks (and symbols) as tokens of their own.
+    pub punctuation: bool,
+    pub output: SegmentOutput,
+}
+
+impl Default for SegmentOptions {
+    fn default() -> Self {
+        Self {
+            language: Language::default(),
+            abbreviations: vec![],
+            punctuation: true,
+            output: SegmentOutput::default(),
+        }
+    }
+}
+
+/// A sentence or a word, with its byte offsets in the text it was found in.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Segment {
+    pub text: String,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Segment {
+    /// The segment of `text` in `start..end`, without surrounding whitespace.
+    fn trimmed(text: &str, start: usize, end: usize) -> Option<Segment> {
+        let slice = &text[start..end];
+        let trimmed = slice.trim_start();
+        let start = start + slice.len() - trimmed.len();
+        let trimmed = trimmed.trim_end();
+        match trimmed.is_empty() {
+            true => None,
+            false => Some(Segment { text: trimmed.to_owned(), start, end: start + trimmed.len() }),
+        }
+    }
+}
+
+/// Splits text into sentences and words along the boundaries of UAX #29,
+/// without breaking sentences after abbreviations.
+#[derive(Debug, Clone)]
+pub struct Segmenter {
+    abbreviations: HashSet<String>,
+    punctuation: bool,
+    output: SegmentOutput,
+}
+
+impl Segmenter {
+    pub fn new(options: &SegmentOptions) -> Self {
+        let mut abbreviations = abbreviations(options.language).iter().map(|a| a.to_string()).collect::<HashSet<_>>();
+        abbreviations.extend(options.abbreviations.iter().map(|a| a.trim().trim_end_matches('.').to_lowercase()));
+        Self { abbreviations, punctuation: options.punctuation, output: options.output }
+    }
+
+    /// Whether `word`, followed by a period, is an abbreviation. `before`
+    /// and `after` are the text around it, which tells whether a single
+    /// capital letter is an initial.
+    fn is_abbreviation(&self, word: &str, before: &str, after: &str) -> bool {
+        let lowercase = word.to_lowercase();
+        let mut chars = word.chars();
+        self.abbreviations.contains(&lowercase)
+            || matches!((chars.next(), chars.next()), (Some(c), None) if c.is_uppercase() && is_initial(before, after))
+    }
+
+    /// Whether the sentence ending at `end` in `text` only ends there because
+    /// of a period after an abbreviation.
+    fn ends_with_abbreviation(&self, text: &str, end: usize) -> bool {
+        match text[..end].trim_end().strip_suffix('.') {
+            Some(body) => {
+                let word = body.rsplit(|c: char| c.is_whitespace() || "([{\"'“‘«".contains(c)).next().unwrap_or("");
+                !word.is_empty() && self.is_abbreviation(word, &body[..body.len() - word.len()], &text[end..])
+            },
+            None => false,
+        }
+    }
+
+    /// The sentences of `text`. Single line breaks don't end a sentence,
+    /// since text is often wrapped, but blank lines do.
+    pub fn sentences(&self, text: &str) -> Vec<Segment> {
+        // Line breaks become spaces of the same length, so the offsets still apply to `text`.
+        let mut unwrapped = text.replace('\n', " ");
+        for blank in BLANK_LINES.find_iter(text) {
+            unwrapped.replace_range(blank.range(), blank.as_str());
+        }
+
+        let mut sentences = vec![];
+        let mut start = None;
+        for (offset, sentence) in unwrapped.split_sentence_bound_indices() {
+            let begin = *start.get_or_insert(offset);
+            if self.ends_with_abbreviation(&unwrapped, offset + sentence.len()) {
+                continue;
+            }
+            start = None;
+            sentences.extend(Segment::trimmed(text, begin, offset + sentence.len()));
+        }
+        if let Some(begin) = start {
+            sentences.extend(Segment::trimmed(text, begin, text.len()));
+        }
+        sentences
+    }
+
+    /// The words of `text`, and its punctuation marks if those are kept.
+    /// A period is kept with the abbreviation before it.
+    pub fn words(&self, text: &str) -> Vec<Segment> {
+        let mut words: Vec<Segment> = vec![];
+        for (start, word) in text.split_word_bound_indices() {
+            if word.trim().is_empty() {
+                continue;
+            }
+            if word == "." {
+                if let Some(last) = words.last_mut() {
+                    if last.end == start && self.is_abbreviation(&last.text, &text[..last.start], &text[start + 1..]) {
+                        last.text.push('.');
+                        last.end += 1;
+                        continue;
+                    }
+                }
+            }
+            if self.punctuation || word.chars().any(char::is_alphanumeric) {
+                words.push(Segment { text: word.to_owned(), start, end: start + word.len() });
+            }
+        }
+        words
+    }
+
+    /// Write out the sentences or words of `text` as chosen by the options.
+    pub fn format(&self, text: &str) -> String {
+        match self.output {
+            SegmentOutput::SentencePerLine => self
+                .sentences(text)
+                .iter()
+                .map(|sentence| sentence.text.split_whitespace().collect::<Vec<_>>().join(" "))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            SegmentOutput::Tokens => self.words(text).iter().map(|word| word.text.as_str()).collect::<Vec<_>>().join(" "),
+            SegmentOutput::TokensJson => serde_json::to_string(&self.words(text)).expect("Segments are always serializable."),
+        }
+    }
+}
+
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    fn segmenter(language: Language) -> Segmenter {
+        Segmenter::new(&SegmentOptions { language, ..Default::default() })
+    }
+
+    fn sentences(segmenter: &Segmenter, text: &str) -> Vec<String> {
+        segmenter.sentences(text).into_iter().map(|sentence| sentence.text).collect()
+    }
+
+    #[test]
+    pub fn test_sentences() {
+        let english = segmenter(Language::English);
+        let text = "Dr. Smith paid $3.50 for it. J. R. R. Tolkien agreed!  Did he?\nYes, e.g. on Mar. 3.";
+        assert_eq!(
+            sentences(&english, text),
+            vec!["Dr. Smith paid $3.50 for it.", "J. R. R. Tolkien agreed!", "Did he?", "Yes, e.g. on Mar. 3."]
+        );
+
+        assert_eq!(sentences(&english, "A heading\n\nThe text"), vec!["A heading", "The text"]);
+        assert_eq!(sentences(&english, "We chose plan B. Then we left."), vec!["We chose plan B.", "Then we left."]);
+        assert_eq!(sentences(&english, "So did I. Then a. Next"), vec!["So did I.", "Then a.", "Next"]);
+        assert_eq!(sentences(&english, "John F. Kennedy spoke."), vec!["John F. Kennedy spoke."]);
+
+        let german = segmenter(Language::German);
+        assert_eq!(sentences(&german, "Das ist z.B. ein Test. Vgl. Nr. 5 unten."), vec!["Das ist z.B. ein Test.", "Vgl. Nr. 5 unten."]);
+    }
+
+    #[test]
+    pub fn test_sentence_offsets() {
+        let text = "  One. Two.";
+        let sentences = segmenter(Language::English).sentences(text);
+        assert_eq!(sentences[1], Segment { text: String::from("Two."), start: 7, end: 11 });
+        assert_eq!(&text[sentences[0].start..sentences[0].end], "One.");
+    }
+
+    #[test]
+    pub fn test_custom_abbreviations() {
+        let text = "Es dauerte approx. Zehn Tage.";
+        assert_eq!(sentences(&segmenter(Language::German), text).len(), 2);
+        let options = SegmentOptions { language: Language::German, abbreviations: vec![String::from("Approx.")], ..Default::default() };
+        assert_eq!(sentences(&Segmenter::new(&options), text), vec![text]);
+    }
+
+    #[test]
+    pub fn test_words() {
+        let text = "Mr. O'Neil can't pay 3.14€, e.g. today.";
+        let words = segmenter(Language::English).words(text);
+        assert_eq!(
+            words.iter().map(|word| word.text.as_str()).collect::<Vec<_>>(),
+            vec!["Mr.", "O'Neil", "can't", "pay", "3.14", "€", ",", "e.g.", "today", "."]
+        );
+        assert_eq!(words[4], Segment { text: String::from("3.14"), start: 21, end: 25 });
+        let words = segmenter(Language::English).words("plan B. Then J. R. Smith");
+        assert_eq!(words.iter().map(|word| word.text.as_str()).collect::<Vec<_>>(), vec!["plan", "B", ".", "Then", "J.", "R.", "Smith"]);
+
+        let options = SegmentOptions { punctuation: false, ..Default::default() };
+        assert_eq!(Segmenter::new(&options).words("Hi, there!").len(), 2);
+    }
+
+    #[test]
+    pub fn test_format() {
+        let text = "First one.\nIt spans\nlines. Last";
+        assert_eq!(segmenter(Language::English).format(text), "First one.\nIt spans lines.\nLast");
+
+        let options = SegmentOptions { output: SegmentOutput::Tokens, ..Default::default() };
+        assert_eq!(Segmenter::new(&options).format("Hi, you."), "Hi , you .");
+
+        let options = SegmentOptions { output: SegmentOutput::TokensJson, ..Default::default() };
+        assert_eq!(
+            Segmenter::new(&options).format("Hi you"),
+            r#"[{"text":"Hi","start":0,"end":2},{"text":"you","start":3,"end":6}]"#
+        );
+    }
+}
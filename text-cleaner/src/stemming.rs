@@ -0,0 +1,184 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use hashbrown::HashMap;
+use rust_stemmers::Algorithm;
+use serde::{Deserialize, Serialize};
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::case::case_fold;
+use crate::dictionary::read_entries;
+use crate::error::Result;
+use crate::language::Language;
+
+/// Options for reducing words to their Snowball stems.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StemOptions {
+    pub language: Language,
+}
+
+/// Options for replacing words by their lemmas, as looked up in a dictionary
+/// of `form<TAB>lemma` lines (or a JSON object). Forms are matched
+/// regardless of case.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LemmaOptions {
+    pub path: Option<PathBuf>,
+    pub entries: BTreeMap<String, String>,
+}
+
+fn algorithm(language: Language) -> Algorithm {
+    match language {
+        Language::English => Algorithm::English,
+        Language::German => Algorithm::German,
+        Language::French => Algorithm::French,
+        Language::Spanish => Algorithm::Spanish,
+        Language::Italian => Algorithm::Italian,
+        Language::Portuguese => Algorithm::Portuguese,
+        Language::Dutch => Algorithm::Dutch,
+        Language::Russian => Algorithm::Russian,
+    }
+}
+
+/// Give `word` the case of `original`: all capitals, a leading capital, or lowercase.
+fn match_case(original: &str, word: &str) -> String {
+    let mut letters = original.chars().filter(|c| c.is_alphabetic());
+    match letters.next() {
+        Some(first) if first.is_uppercase() => match letters.clone().next().is_some() && letters.all(char::is_uppercase) {
+            true => word.to_uppercase(),
+            false => {
+                let mut chars = word.chars();
+                chars.next().map(|c| c.to_uppercase().chain(chars).collect()).unwrap_or_default()
+            },
+        },
+        _ => word.to_owned(),
+    }
+}
+
+/// Run `map` over the words of `text` (by UAX #29 word boundaries, leaving
+/// numbers out), keeping everything between them as it is.
+///
+/// A possessive `'s` is mapped without, and put back after. A word followed
+/// by a lone apostrophe, as in "the cats' toys", is left as it is, since the
+/// apostrophe may as well close a quotation.
+fn map_words<F>(text: &str, map: F) -> String
+where
+    F: Fn(&str) -> Option<String>
+{
+    let mut result = String::with_capacity(text.len());
+    let mut segments = text.split_word_bounds().peekable();
+    while let Some(segment) = segments.next() {
+        let is_word = segment.chars().any(char::is_alphabetic);
+        let before_apostrophe = segments.peek().is_some_and(|next| matches!(*next, "'" | "’"));
+        if !is_word || before_apostrophe {
+            result.push_str(segment);
+            continue;
+        }
+        let (word, possessive) = match segment.char_indices().rev().nth(1) {
+            Some((i, '\'' | '’')) if i > 0 && segment.ends_with(['s', 'S']) => segment.split_at(i),
+            _ => (segment, ""),
+        };
+        match map(word) {
+            Some(mapped) => {
+                result.push_str(&mapped);
+                result.push_str(possessive);
+            },
+            None => result.push_str(segment),
+        }
+    }
+    result
+}
+
+/// Reduces words to their stems with the Snowball algorithm of a language.
+pub struct Stemmer {
+    stemmer: rust_stemmers::Stemmer,
+}
+
+impl Stemmer {
+    pub fn new(options: &StemOptions) -> Self {
+        Self { stemmer: rust_stemmers::Stemmer::create(algorithm(options.language)) }
+    }
+
+    pub fn stem(&self, word: &str) -> String {
+        let lowercase = word.to_lowercase();
+        match_case(word, &self.stemmer.stem(&lowercase))
+    }
+
+    pub fn apply(&self, text: &str) -> String {
+        map_words(text, |word| Some(self.stem(word)))
+    }
+}
+
+/// Replaces words by their lemmas from a dictionary. Words are looked up
+/// regardless of case, by Unicode full case folding, like the keys of a
+/// [`crate::dictionary::Dictionary`].
+#[derive(Debug, Clone, Default)]
+pub struct Lemmatizer {
+    lemmas: HashMap<String, String>,
+}
+
+impl Lemmatizer {
+    pub fn new(options: &LemmaOptions) -> Result<Self> {
+        let mut entries = match &options.path {
+            Some(path) => read_entries(path)?,
+            None => vec![],
+        };
+        entries.extend(options.entries.iter().map(|(form, lemma)| (form.clone(), lemma.clone())));
+        let lemmas = entries.into_iter().map(|(form, lemma)| (case_fold(form.trim(), None), lemma.trim().to_owned())).collect();
+        Ok(Self { lemmas })
+    }
+
+    pub fn lemma(&self, word: &str) -> Option<String> {
+        self.lemmas.get(&case_fold(word, None)).map(|lemma| match_case(word, lemma))
+    }
+
+    pub fn apply(&self, text: &str) -> String {
+        map_words(text, |word| self.lemma(word))
+    }
+}
+
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    fn stemmer(language: Language) -> Stemmer {
+        Stemmer::new(&StemOptions { language })
+    }
+
+    #[test]
+    pub fn test_stem() {
+        let english = stemmer(Language::English);
+        assert_eq!(english.apply("Running, the runners ran quickly!\n  Generalizations: 42"), "Run, the runner ran quick!\n  General: 42");
+        assert_eq!(english.stem("CONNECTIONS"), "CONNECT");
+
+        assert_eq!(stemmer(Language::German).apply("Die Häuser"), "Die Haus");
+        assert_eq!(stemmer(Language::Russian).apply("книги"), "книг");
+    }
+
+    #[test]
+    pub fn test_lemmatize() {
+        let entries = BTreeMap::from([
+            (String::from("went"), String::from("go")),
+            (String::from("mice"), String::from("mouse")),
+            (String::from("better"), String::from("good")),
+        ]);
+        let lemmatizer = Lemmatizer::new(&LemmaOptions { path: None, entries }).unwrap();
+        assert_eq!(lemmatizer.apply("Mice went  out; BETTER (maybe)."), "Mouse go  out; GOOD (maybe).");
+
+        let entries = BTreeMap::from([(String::from("Straßen"), String::from("Straße"))]);
+        let lemmatizer = Lemmatizer::new(&LemmaOptions { path: None, entries }).unwrap();
+        assert_eq!(lemmatizer.apply("STRASSEN, Straßen, strassen"), "STRASSE, Straße, Straße");
+    }
+
+    #[test]
+    pub fn test_lemma_file() {
+        let path = std::env::temp_dir().join("text-cleaner-lemmas.tsv");
+        std::fs::write(&path, "# form\tlemma\nwas\tbe\ncats\tcat\nchildren\tchild\n").unwrap();
+        let lemmatizer = Lemmatizer::new(&LemmaOptions { path: Some(path.clone()), ..Default::default() }).unwrap();
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(lemmatizer.apply("It was the cats' toy, not the children’s cats"), "It be the cats' toy, not the child’s cat");
+        assert_eq!(lemmatizer.apply("'cats' was all she said"), "'cats' be all she said");
+    }
+}
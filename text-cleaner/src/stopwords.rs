@@ -0,0 +1,165 @@
+use std::path::PathBuf;
+
+use hashbrown::HashSet;
+use serde::{Deserialize, Serialize};
+use unicode_normalization::UnicodeNormalization;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::error::Result;
+use crate::language::Language;
+
+/// The bundled stopword list of a language, one word per line.
+fn bundled(language: Language) -> &'static str {
+    match language {
+        Language::English => include_str!("../data/stopwords/en.txt"),
+        Language::German => include_str!("../data/stopwords/de.txt"),
+        Language::French => include_str!("../data/stopwords/fr.txt"),
+        Language::Spanish => include_str!("../data/stopwords/es.txt"),
+        Language::Italian => include_str!("../data/stopwords/it.txt"),
+        Language::Portuguese => include_str!("../data/stopwords/pt.txt"),
+        Language::Dutch => include_str!("../data/stopwords/nl.txt"),
+        Language::Russian => include_str!("../data/stopwords/ru.txt"),
+    }
+}
+
+/// Options for removing stopwords.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StopwordOptions {
+    /// The languages whose bundled lists are used.
+    pub languages: Vec<Language>,
+    /// Files with one stopword per line. Lines starting with `#` are comments.
+    pub files: Vec<PathBuf>,
+    pub words: Vec<String>,
+}
+
+impl Default for StopwordOptions {
+    fn default() -> Self {
+        Self { languages: vec![Language::English], files: vec![], words: vec![] }
+    }
+}
+
+/// The form words are compared in: NFKC-normalized, lowercase, and with
+/// typographic apostrophes made straight.
+fn normalize(word: &str) -> String {
+    word.nfkc().collect::<String>().to_lowercase().replace('’', "'")
+}
+
+/// A set of words to remove from text.
+#[derive(Debug, Clone, Default)]
+pub struct Stopwords {
+    words: HashSet<String>,
+}
+
+impl Stopwords {
+    pub fn new(options: &StopwordOptions) -> Result<Self> {
+        let mut stopwords = Self::default();
+        for language in &options.languages {
+            stopwords.extend(bundled(*language));
+        }
+        for path in &options.files {
+            stopwords.extend(&std::fs::read_to_string(path)?);
+        }
+        stopwords.words.extend(options.words.iter().map(|word| normalize(word.trim())));
+        Ok(stopwords)
+    }
+
+    fn extend(&mut self, list: &str) {
+        let words = list.lines().map(str::trim).filter(|line| !line.is_empty() && !line.starts_with('#'));
+        self.words.extend(words.map(normalize));
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        self.words.contains(&normalize(word))
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Remove the stopwords of `text`, along with the spaces that separated
+    /// them from what follows. Punctuation and line breaks are kept.
+    pub fn remove(&self, text: &str) -> String {
+        let mut result = String::with_capacity(text.len());
+        let mut removed = false;
+        for segment in text.split_word_bounds() {
+            let is_space = segment.chars().all(char::is_whitespace);
+            if is_space && segment.contains('\n') {
+                if removed {
+                    result.truncate(result.trim_end_matches([' ', '\t']).len());
+                }
+                result.push_str(segment);
+            } else if is_space {
+                if !removed || result.ends_with(char::is_alphanumeric) {
+                    result.push_str(segment);
+                }
+                continue;
+            } else if self.contains(segment) {
+                removed = true;
+                continue;
+            } else {
+                if removed && segment.starts_with(|c: char| ".,;:!?)]}»”…".contains(c)) {
+                    result.truncate(result.trim_end_matches([' ', '\t']).len());
+                }
+                result.push_str(segment);
+            }
+            removed = false;
+        }
+        result
+    }
+}
+
+pub fn remove_stopwords(text: &str, options: &StopwordOptions) -> Result<String> {
+    Ok(Stopwords::new(options)?.remove(text))
+}
+
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    fn stopwords(languages: Vec<Language>) -> Stopwords {
+        Stopwords::new(&StopwordOptions { languages, ..Default::default() }).unwrap()
+    }
+
+    #[test]
+    pub fn test_bundled_lists() {
+        for language in Language::ALL {
+            assert!(stopwords(vec![language]).len() > 90, "{:?}", language);
+        }
+        let stopwords = stopwords(vec![Language::German, Language::French]);
+        assert!(stopwords.contains("Und") && stopwords.contains("ÉTAIT"));
+        assert!(!stopwords.contains("the"));
+    }
+
+    #[test]
+    pub fn test_normalized_matching() {
+        let english = stopwords(vec![Language::English]);
+        assert!(english.contains("DON’T"));
+        assert!(english.contains("ｔｈｅ"));
+    }
+
+    #[test]
+    pub fn test_remove() {
+        let english = stopwords(vec![Language::English]);
+        assert_eq!(english.remove("The cat and the dog."), "cat dog.");
+        assert_eq!(english.remove("A cat, (the dog) is\nnot here!"), "cat, (dog)\n!");
+        assert_eq!(english.remove("  keep   spacing  \n"), "  keep   spacing  \n");
+    }
+
+    #[test]
+    pub fn test_custom_lists() {
+        let path = std::env::temp_dir().join("text-cleaner-stopwords.txt");
+        std::fs::write(&path, "# custom\nfoo\n\nBar\n").unwrap();
+        let options = StopwordOptions { languages: vec![], files: vec![path.clone()], words: vec![String::from("baz")] };
+        assert_eq!(remove_stopwords("foo bar baz qux", &options).unwrap(), "qux");
+        std::fs::remove_file(path).unwrap();
+
+        let options = StopwordOptions { files: vec![PathBuf::from("/nonexistent/stopwords.txt")], ..Default::default() };
+        assert!(remove_stopwords("text", &options).is_err());
+    }
+}
@@ -0,0 +1,202 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use unicode_normalization::UnicodeNormalization;
+use unicode_script::Script;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::error::{Error, Result};
+use crate::script::{cluster_extension, is_neutral, parse_script};
+
+/// How the characters of a script are transliterated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Scheme {
+    /// Closest ASCII spelling, from the bundled Unidecode tables.
+    #[default]
+    Unidecode,
+    /// ISO 9:1995, a one-to-one Latin transliteration of Cyrillic.
+    Iso9,
+    /// Leave the script untouched.
+    Keep,
+}
+
+/// Options for transliterating text.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TransliterationOptions {
+    /// The scheme used for every script that isn't listed in `scripts`.
+    pub default: Scheme,
+    /// Per-script schemes, keyed by full (`"Cyrillic"`) or short (`"Cyrl"`) script name.
+    pub scripts: BTreeMap<String, Scheme>,
+}
+
+/// The ISO 9:1995 spelling of an uppercase Cyrillic letter.
+fn iso9_upper(c: char) -> Option<&'static str> {
+    let latin = match c {
+        'А' => "A",
+        'Б' => "B",
+        'В' => "V",
+        'Г' => "G",
+        'Ґ' => "G\u{0300}",
+        'Д' => "D",
+        'Ѓ' => "Ǵ",
+        'Ђ' => "Đ",
+        'Е' => "E",
+        'Ё' => "Ë",
+        'Є' => "Ê",
+        'Ж' => "Ž",
+        'З' => "Z",
+        'Ѕ' => "Ẑ",
+        'И' => "I",
+        'І' => "Ì",
+        'Ї' => "Ï",
+        'Й' => "J",
+        'Ј' => "J\u{030C}",
+        'К' => "K",
+        'Л' => "L",
+        'Љ' => "L\u{0302}",
+        'М' => "M",
+        'Н' => "N",
+        'Њ' => "N\u{0302}",
+        'О' => "O",
+        'П' => "P",
+        'Р' => "R",
+        'С' => "S",
+        'Т' => "T",
+        'Ќ' => "Ḱ",
+        'Ћ' => "Ć",
+        'У' => "U",
+        'Ў' => "Ǔ",
+        'Ф' => "F",
+        'Х' => "H",
+        'Ц' => "C",
+        'Ч' => "Č",
+        'Џ' => "D\u{0302}",
+        'Ш' => "Š",
+        'Щ' => "Ŝ",
+        'Ъ' => "ʺ",
+        'Ы' => "Y",
+        'Ь' => "ʹ",
+        'Э' => "È",
+        'Ю' => "Û",
+        'Я' => "Â",
+        'Ѣ' => "Ě",
+        'Ѳ' => "F\u{0300}",
+        'Ѵ' => "Ỳ",
+        _ => return None,
+    };
+    Some(latin)
+}
+
+/// Transliterate Cyrillic per ISO 9, falling back to Unidecode
+/// for anything the standard doesn't cover.
+fn iso9(cluster: &str) -> String {
+    let mut result = String::new();
+    for c in cluster.nfc() {
+        let mut upper = c.to_uppercase();
+        let latin = match (upper.next(), upper.next()) {
+            (Some(u), None) => iso9_upper(u),
+            _ => None,
+        };
+        match latin {
+            Some(latin) if c.is_uppercase() => result.push_str(latin),
+            Some(latin) => result.push_str(&latin.to_lowercase()),
+            None => result.push_str(&unidecode(c.encode_utf8(&mut [0; 4]))),
+        }
+    }
+    result.nfc().collect()
+}
+
+fn unidecode(cluster: &str) -> String {
+    deunicode::deunicode_with_tofu(cluster, "")
+}
+
+/// Transliterates text script by script.
+#[derive(Debug, Clone, Default)]
+pub struct Transliterator {
+    default: Scheme,
+    scripts: Vec<(Script, Scheme)>,
+}
+
+impl Transliterator {
+    pub fn new(options: &TransliterationOptions) -> Result<Self> {
+        let mut scripts = vec![];
+        for (name, scheme) in &options.scripts {
+            let script = parse_script(name)?;
+            if *scheme == Scheme::Iso9 && script != Script::Cyrillic {
+                return Err(Error::UnsupportedScheme { script: name.clone(), scheme: String::from("iso9") });
+            }
+            scripts.push((script, *scheme));
+        }
+        if options.default == Scheme::Iso9 {
+            return Err(Error::UnsupportedScheme { script: String::from("default"), scheme: String::from("iso9") });
+        }
+        Ok(Self { default: options.default, scripts })
+    }
+
+    fn scheme(&self, cluster: &str) -> Scheme {
+        let ext = cluster_extension(cluster);
+        if is_neutral(ext) {
+            return self.default;
+        }
+        self.scripts
+            .iter()
+            .find(|(script, _)| ext.contains_script(*script))
+            .map_or(self.default, |(_, scheme)| *scheme)
+    }
+
+    pub fn apply(&self, text: &str) -> String {
+        let mut result = String::with_capacity(text.len());
+        for cluster in text.graphemes(true) {
+            if cluster.is_ascii() {
+                result.push_str(cluster);
+                continue;
+            }
+            match self.scheme(cluster) {
+                Scheme::Keep => result.push_str(cluster),
+                Scheme::Unidecode => result.push_str(&unidecode(cluster)),
+                Scheme::Iso9 => result.push_str(&iso9(cluster)),
+            }
+        }
+        result
+    }
+}
+
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    fn transliterate(text: &str, scripts: &[(&str, Scheme)]) -> String {
+        let options = TransliterationOptions {
+            scripts: scripts.iter().map(|(name, scheme)| (name.to_string(), *scheme)).collect(),
+            ..Default::default()
+        };
+        Transliterator::new(&options).unwrap().apply(text)
+    }
+
+    #[test]
+    pub fn test_unidecode() {
+        assert_eq!(transliterate("Москва, Αθήνα, Straße", &[]), "Moskva, Athena, Strasse");
+    }
+
+    #[test]
+    pub fn test_iso9() {
+        assert_eq!(transliterate("Щука и Ёж", &[("Cyrillic", Scheme::Iso9)]), "Ŝuka i Ëž");
+    }
+
+    #[test]
+    pub fn test_keep_script() {
+        assert_eq!(transliterate("Αθήνα Москва", &[("Greek", Scheme::Keep)]), "Αθήνα Moskva");
+    }
+
+    #[test]
+    pub fn test_iso9_is_only_for_cyrillic() {
+        let options = TransliterationOptions {
+            scripts: BTreeMap::from([(String::from("Greek"), Scheme::Iso9)]),
+            ..Default::default()
+        };
+        assert!(matches!(Transliterator::new(&options), Err(Error::UnsupportedScheme { .. })));
+    }
+}
@@ -0,0 +1,262 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Options for wrapping paragraphs to a width.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WrapOptions {
+    /// The most columns a line may take, counting CJK characters and emoji as two.
+    pub width: usize,
+    /// Split words wider than a line. URLs are never split.
+    pub break_long_words: bool,
+}
+
+impl Default for WrapOptions {
+    fn default() -> Self {
+        Self { width: 72, break_long_words: false }
+    }
+}
+
+lazy_static! {
+    /// A list item: a bullet, or a number or single letter followed by `.` or `)`.
+    static ref LIST_ITEM: Regex = Regex::new(r"^(\s*)((?:[-*+•‣◦▪–]|\(?\d{1,9}[.)]|\(?[a-zA-Z][.)])\s+)").unwrap();
+    static ref URL: Regex = Regex::new(r"(?i)^\W*(?:[a-z][a-z0-9+.-]*://|www\.)").unwrap();
+}
+
+fn display_width(text: &str) -> usize {
+    text.chars().map(|c| if c == '\t' { 4 } else { c.width().unwrap_or(0) }).sum()
+}
+
+/// Whether a line may break on either side of `c`, as between CJK characters.
+fn is_wide(c: char) -> bool {
+    c.width() == Some(2) && c.is_alphabetic()
+}
+
+/// A part of a paragraph that is never broken, and whether a space comes before it.
+struct Piece<'a> {
+    text: &'a str,
+    space: bool,
+}
+
+/// A paragraph or list item: the prefix of its first line, the prefix of
+/// the lines that follow, and its contents.
+struct Item<'a> {
+    first: String,
+    rest: String,
+    pieces: Vec<Piece<'a>>,
+}
+
+impl<'a> Item<'a> {
+    fn new(line: &'a str) -> Self {
+        let (first, rest, content) = match LIST_ITEM.captures(line) {
+            Some(captures) => {
+                let marker = captures.get(0).unwrap();
+                let indent = &captures[1];
+                (marker.as_str().to_owned(), format!("{}{}", indent, " ".repeat(display_width(&captures[2]))), &line[marker.end()..])
+            },
+            None => {
+                let content = line.trim_start();
+                let indent = &line[..line.len() - content.len()];
+                (indent.to_owned(), indent.to_owned(), content)
+            },
+        };
+        let mut item = Self { first, rest, pieces: vec![] };
+        item.push_line(content);
+        item
+    }
+
+    /// Add the pieces of a line, joined to the previous line by a space
+    /// unless both sides are wide characters.
+    fn push_line(&mut self, line: &'a str) {
+        let last = self.pieces.last().and_then(|piece| piece.text.chars().last());
+        for (n, word) in line.split_whitespace().enumerate() {
+            let first = word.chars().next().unwrap();
+            let space = match n {
+                0 => last.is_some_and(|last| !(is_wide(last) && is_wide(first))),
+                _ => true,
+            };
+            let mut start = 0;
+            let mut previous: Option<char> = None;
+            for (i, c) in word.char_indices() {
+                if let Some(p) = previous {
+                    if (is_wide(p) || is_wide(c)) && c.is_alphanumeric() && !"([{「『（“‘".contains(p) {
+                        self.pieces.push(Piece { text: &word[start..i], space: space && start == 0 });
+                        start = i;
+                    }
+                }
+                previous = Some(c);
+            }
+            self.pieces.push(Piece { text: &word[start..], space: space && start == 0 });
+        }
+    }
+
+    /// Fill lines of up to `width` columns with the pieces of the item.
+    fn fill(&self, width: Option<usize>, break_long_words: bool, lines: &mut Vec<String>) {
+        let mut line = self.first.clone();
+        let mut column = display_width(&line);
+        let mut empty = true;
+        for piece in &self.pieces {
+            let piece_width = piece.text.width();
+            let space = !empty && piece.space;
+            if !empty && width.is_some_and(|width| column + usize::from(space) + piece_width > width) {
+                lines.push(std::mem::replace(&mut line, self.rest.clone()));
+                column = display_width(&line);
+                empty = true;
+            } else if space {
+                line.push(' ');
+                column += 1;
+            }
+
+            match width {
+                Some(width) if break_long_words && column + piece_width > width && !URL.is_match(piece.text) => {
+                    for c in piece.text.chars() {
+                        let char_width = c.width().unwrap_or(0);
+                        if !empty && column + char_width > width {
+                            lines.push(std::mem::replace(&mut line, self.rest.clone()));
+                            column = display_width(&line);
+                        }
+                        line.push(c);
+                        column += char_width;
+                        empty = false;
+                    }
+                },
+                _ => {
+                    line.push_str(piece.text);
+                    column += piece_width;
+                    empty = false;
+                },
+            }
+        }
+        lines.push(line);
+    }
+}
+
+fn is_fence(line: &str) -> bool {
+    let line = line.trim_start();
+    line.starts_with("```") || line.starts_with("~~~")
+}
+
+/// Re-fill the paragraphs of `text`, breaking lines at `width` or, without
+/// one, not at all. List items are filled on their own, with their following
+/// lines indented past the marker, and so is every line for which
+/// `keep_break(previous, line)` is true. Blank lines and fenced code blocks
+/// are kept as they are.
+fn reflow<F>(text: &str, width: Option<usize>, break_long_words: bool, keep_break: F) -> String
+where
+    F: Fn(&str, &str) -> bool
+{
+    let mut lines = vec![];
+    let mut items: Vec<Item> = vec![];
+    let mut fenced = false;
+    let flush = |items: &mut Vec<Item>, lines: &mut Vec<String>| {
+        for item in items.drain(..) {
+            item.fill(width, break_long_words, lines);
+        }
+    };
+
+    let mut previous: Option<&str> = None;
+    for line in text.split('\n') {
+        if fenced || is_fence(line) || line.trim().is_empty() {
+            flush(&mut items, &mut lines);
+            fenced ^= is_fence(line);
+            lines.push(line.to_owned());
+            previous = None;
+            continue;
+        }
+        match previous {
+            Some(previous) if !LIST_ITEM.is_match(line) && !keep_break(previous, line) => {
+                items.last_mut().unwrap().push_line(line);
+            },
+            _ => items.push(Item::new(line)),
+        }
+        previous = Some(line);
+    }
+    flush(&mut items, &mut lines);
+    lines.join("\n")
+}
+
+/// Wrap the paragraphs of `text` to `options.width` columns, keeping their
+/// indentation and list markers.
+pub fn wrap_text(text: &str, options: &WrapOptions) -> String {
+    reflow(text, Some(options.width.max(1)), options.break_long_words, |_, _| false)
+}
+
+/// Join the wrapped lines of each paragraph and list item back into one.
+pub fn unwrap_text(text: &str) -> String {
+    reflow(text, None, false, |_, _| false)
+}
+
+/// Like [`unwrap_text`], but also keep the line break between two lines
+/// when `keep_break(line, next)` is true, as after a heading.
+pub fn unwrap_text_keeping<F>(text: &str, keep_break: F) -> String
+where
+    F: Fn(&str, &str) -> bool
+{
+    reflow(text, None, false, keep_break)
+}
+
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    fn wrap(text: &str, width: usize) -> String {
+        wrap_text(text, &WrapOptions { width, ..Default::default() })
+    }
+
+    #[test]
+    pub fn test_wrap_paragraphs() {
+        let text = "The quick brown fox jumps over the lazy dog.\n\n  An indented paragraph\nthat was wrapped before.\n";
+        assert_eq!(
+            wrap(text, 20),
+            "The quick brown fox\njumps over the lazy\ndog.\n\n  An indented\n  paragraph that was\n  wrapped before.\n"
+        );
+    }
+
+    #[test]
+    pub fn test_list_items() {
+        let text = "Changes:\n- first item that is long enough to wrap\n  - nested item\n10. numbered item here";
+        assert_eq!(
+            wrap(text, 20),
+            "Changes:\n- first item that is\n  long enough to\n  wrap\n  - nested item\n10. numbered item\n    here"
+        );
+    }
+
+    #[test]
+    pub fn test_urls_and_long_words() {
+        let text = "see https://example.com/a/very/long/path for details";
+        assert_eq!(wrap(text, 10), "see\nhttps://example.com/a/very/long/path\nfor\ndetails");
+
+        let options = WrapOptions { width: 10, break_long_words: true };
+        assert_eq!(wrap_text("a supercalifragilistic word", &options), "a\nsupercalif\nragilistic\nword");
+        assert_eq!(wrap_text(text, &options), "see\nhttps://example.com/a/very/long/path\nfor\ndetails");
+    }
+
+    #[test]
+    pub fn test_display_width() {
+        assert_eq!(wrap("日本語のテキストです", 8), "日本語の\nテキスト\nです");
+        assert_eq!(wrap("你好，世界。再见", 6), "你好，\n世界。\n再见");
+        assert_eq!(wrap("ok 👍 ok 👍 ok", 6), "ok 👍\nok 👍\nok");
+    }
+
+    #[test]
+    pub fn test_code_blocks_are_kept() {
+        let text = "some text\n```\nlet x = a very long line of code;\n```";
+        assert_eq!(wrap(text, 10), "some text\n```\nlet x = a very long line of code;\n```");
+    }
+
+    #[test]
+    pub fn test_unwrap() {
+        let text = "The quick brown fox\njumps over the lazy\ndog.\n\n- first item that is\n  long enough\n- second\n";
+        assert_eq!(unwrap_text(text), "The quick brown fox jumps over the lazy dog.\n\n- first item that is long enough\n- second\n");
+        assert_eq!(unwrap_text("日本語の\nテキスト"), "日本語のテキスト");
+
+        assert_eq!(unwrap_text("Steps:\na) mix\n(2) bake it\nslowly"), "Steps:\na) mix\n(2) bake it slowly");
+        assert_eq!(unwrap_text_keeping("TITLE\nbody\ntext", |line, _| line == "TITLE"), "TITLE\nbody text");
+
+        let long = "word ".repeat(40);
+        assert_eq!(unwrap_text(&wrap(long.trim(), 30)), long.trim());
+    }
+}
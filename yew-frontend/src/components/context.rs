@@ -12,13 +12,16 @@ pub struct ContextProps {
 pub fn context_comp(props: &ContextProps) -> Html {
     let text = use_reducer(TextInput::new);
     let config = use_reducer(ProcessorConfigNames::new);
+    let rules = use_reducer(ReplaceRules::new);
 
     html! {
         <ContextProvider<ProcessorConfigNamesContext> context={config}>
             <ContextProvider<TextInputContext> context={text}>
-                <div class="h-full w-full">
-                    { for props.children.iter() }
-                </div>    
+                <ContextProvider<ReplaceRulesContext> context={rules}>
+                    <div class="h-full w-full">
+                        { for props.children.iter() }
+                    </div>    
+                </ContextProvider<ReplaceRulesContext>>
             </ContextProvider<TextInputContext>>
         </ContextProvider<ProcessorConfigNamesContext>>
     }
@@ -2,8 +2,10 @@ mod context;
 mod textboxes;
 mod navbar;
 mod settings;
+mod rules;
 
 pub use context::*;
 pub use textboxes::*;
 pub use navbar::*;
-pub use settings::*;
\ No newline at end of file
+pub use settings::*;
+pub use rules::*;
\ No newline at end of file
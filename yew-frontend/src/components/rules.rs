@@ -0,0 +1,133 @@
+use std::ops::Range;
+
+use text_cleaner::replace::ReplaceRule;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use crate::contexts::*;
+
+/// Split `text` into plain and highlighted pieces according to `ranges`.
+pub fn highlight(text: &str, ranges: &[Range<usize>]) -> Html {
+    let mut pieces = vec![];
+    let mut last = 0;
+    for range in ranges {
+        if range.start > last {
+            pieces.push(html! { {&text[last..range.start]} });
+        }
+        pieces.push(html! { <mark class="bg-secondary text-secondary-content">{&text[range.clone()]}</mark> });
+        last = range.end;
+    }
+    if last < text.len() {
+        pieces.push(html! { {&text[last..]} });
+    }
+    html! { for pieces }
+}
+
+#[derive(PartialEq, Properties)]
+pub struct RuleEditorProps {
+    pub index: usize,
+    pub rule: ReplaceRule,
+}
+
+#[function_component(RuleEditor)]
+pub fn rule_editor(props: &RuleEditorProps) -> Html {
+    let rules = use_context::<ReplaceRulesContext>().expect("Couldn't find a ReplaceRulesContext provider.");
+    let text_input = use_context::<TextInputContext>().expect("Couldn't find a TextInputContext provider.");
+
+    let index = props.index;
+    let rule = props.rule.clone();
+
+    let update = {
+        let rules = rules.clone();
+        let rule = rule.clone();
+        move |edit: fn(&mut ReplaceRule, &HtmlInputElement)| {
+            let rules = rules.clone();
+            let rule = rule.clone();
+            Callback::from(move |e: Event| {
+                let input: HtmlInputElement = e.target_unchecked_into();
+                let mut rule = rule.clone();
+                edit(&mut rule, &input);
+                rules.dispatch(RulesAction::Update(index, rule));
+            })
+        }
+    };
+
+    let on_remove = {
+        let rules = rules.clone();
+        Callback::from(move |_| rules.dispatch(RulesAction::Remove(index)))
+    };
+
+    let preview = match rule.pattern.is_empty() {
+        true => html! {},
+        false => match rule.compile() {
+            Ok(compiled) => {
+                let ranges = compiled.matches(&text_input.raw);
+                html! {
+                    <>
+                        <p class="text-xs italic">{format!("{} match(es)", ranges.len())}</p>
+                        <pre class="text-xs whitespace-pre-wrap max-h-32 overflow-auto">{highlight(&text_input.raw, &ranges)}</pre>
+                    </>
+                }
+            },
+            Err(e) => html! { <p class="text-xs text-error">{e.to_string()}</p> }
+        }
+    };
+
+    html! {
+        <div class="space-y-1 border border-base-300 rounded-md p-2">
+            <div class="flex gap-2">
+                <input
+                    type="text"
+                    class="input input-bordered input-sm w-1/2"
+                    placeholder="Find"
+                    value={rule.pattern.clone()}
+                    onchange={update(|rule, input| rule.pattern = input.value())}
+                />
+                <input
+                    type="text"
+                    class="input input-bordered input-sm w-1/2"
+                    placeholder="Replace with"
+                    value={rule.replacement.clone()}
+                    onchange={update(|rule, input| rule.replacement = input.value())}
+                />
+            </div>
+            <div class="flex items-center gap-2">
+                <input type="checkbox" class="checkbox checkbox-xs" checked={rule.regex}
+                    onchange={update(|rule, input| rule.regex = input.checked())} />
+                <label class="label text-xs">{"Regex"}</label>
+                <input type="checkbox" class="checkbox checkbox-xs" checked={rule.case_insensitive}
+                    onchange={update(|rule, input| rule.case_insensitive = input.checked())} />
+                <label class="label text-xs">{"Ignore case"}</label>
+                <input type="checkbox" class="checkbox checkbox-xs" checked={rule.multiline}
+                    onchange={update(|rule, input| rule.multiline = input.checked())} />
+                <label class="label text-xs">{"Multiline"}</label>
+                <button class="btn btn-ghost btn-xs ml-auto" onclick={on_remove}>{"Remove"}</button>
+            </div>
+            { preview }
+        </div>
+    }
+}
+
+#[function_component(RulesEditor)]
+pub fn rules_editor() -> Html {
+    let rules = use_context::<ReplaceRulesContext>().expect("Couldn't find a ReplaceRulesContext provider.");
+
+    let on_add = {
+        let rules = rules.clone();
+        Callback::from(move |_| rules.dispatch(RulesAction::Add))
+    };
+
+    html! {
+        <div class="w-full space-y-4">
+            <div class="flex justify-between items-center">
+                <h1 class="text-2xl">{"Find & Replace"}</h1>
+                <button class="btn btn-secondary btn-sm rounded-md" onclick={on_add}>{"Add rule"}</button>
+            </div>
+            <div class="px-4 space-y-2">
+                { for rules.rules.iter().enumerate().map(|(index, rule)| html! {
+                    <RuleEditor index={index} rule={rule.clone()} />
+                })}
+            </div>
+        </div>
+    }
+}
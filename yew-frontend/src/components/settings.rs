@@ -1,14 +1,18 @@
 
 use yew::{prelude::*, context::ContextHandle};
 use hashbrown::{HashMap};
+use text_cleaner::language::Language;
+use web_sys::HtmlSelectElement;
 
-use crate::contexts::{ProcessorConfigNamesContext, ProcessorConfigNames};
+use crate::contexts::{ConfigAction, ProcessorConfigNamesContext, ProcessorConfigNames};
+use crate::components::RulesEditor;
 
 
 #[derive(Debug, Clone)]
 pub enum Msg {
     ProcessorConfigNamesContextUpdated(ProcessorConfigNamesContext),
-    Refresh(String)
+    Refresh(String),
+    SetLanguage(Language),
 }
 
 pub struct Settings {
@@ -82,10 +86,23 @@ impl Component for Settings {
 
         let check_boxes_ordered = self.check_boxes_ordered.clone();
 
+        let on_language_change = ctx.link().batch_callback(|e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            Language::from_code(&select.value()).map(Msg::SetLanguage)
+        });
+
         html! {
-            <div class="h-[90%] w-full space-y-8">
+            <div class="h-[90%] w-full space-y-8 overflow-auto">
                 <h1 class="text-2xl">{"Settings"}</h1>
-                <form id="settings" class="h-[90%] px-4 space-y-2 overflow-auto">
+                <div class="px-4 flex items-center gap-2">
+                    <label class="label" for="language">{"Language"}</label>
+                    <select id="language" class="select select-bordered select-sm" onchange={on_language_change}>
+                        { for Language::ALL.iter().map(|language| html! {
+                            <option value={language.code()} selected={*language == self.config.language}>{language.name()}</option>
+                        })}
+                    </select>
+                </div>
+                <form id="settings" class="px-4 space-y-2">
                     { for check_boxes_ordered.into_iter().map(|(key, value)| {
 
                         let key_cp = key.clone();
@@ -106,6 +123,7 @@ impl Component for Settings {
                         }
                     })}
                 </form>
+                <RulesEditor/>
             </div>
         }
     }
@@ -117,7 +135,10 @@ impl Component for Settings {
                 self.config = processor_config_names;
             },
             Msg::Refresh(key) => {
-                self.config.dispatch(key);
+                self.config.dispatch(ConfigAction::Toggle(key));
+            },
+            Msg::SetLanguage(language) => {
+                self.config.dispatch(ConfigAction::SetLanguage(language));
             }
         }
         true
@@ -20,8 +20,10 @@ pub struct InputOutputBox {
     output_node_ref: NodeRef,
     text_input: TextInputContext,
     processor_config_names: ProcessorConfigNamesContext,
+    replace_rules: ReplaceRulesContext,
     _text_input_context_handle: ContextHandle<TextInputContext>,
     _processor_config_names_context_handle: ContextHandle<ProcessorConfigNamesContext>,
+    _replace_rules_context_handle: ContextHandle<ReplaceRulesContext>,
     processor: TextProcessor
 }
 
@@ -32,6 +34,7 @@ pub enum Msg {
     Copied(String),
     TextInputContextUpdated(TextInputContext),
     ProcessorConfigNamesContextUpdated(ProcessorConfigNamesContext),
+    ReplaceRulesContextUpdated(ReplaceRulesContext),
 }
 
 impl Component for InputOutputBox {
@@ -59,16 +62,28 @@ impl Component for InputOutputBox {
             .callback(Msg::ProcessorConfigNamesContextUpdated)
         )
         .expect("Couldn't find a ProcessorConfigNamesContext provider.");
+
+        let (replace_rules, replace_rules_ctx_handle) = 
+        ctx
+        .link()
+        .context(
+            ctx
+            .link()
+            .callback(Msg::ReplaceRulesContextUpdated)
+        )
+        .expect("Couldn't find a ReplaceRulesContext provider.");
         // let text = use_context::<TextInput>().expect("Couldn't find a TextInput context");
 
         Self {
             input_node_ref: NodeRef::default(),
             output_node_ref: NodeRef::default(),
             text_input,
-            processor: TextProcessor::with_config_names(processor_config_names.deref()).unwrap(),
+            processor: TextProcessor::with_config_names(processor_config_names.deref()).unwrap().with_rules(replace_rules.deref()),
             processor_config_names,
+            replace_rules,
             _text_input_context_handle: text_input_ctx_handle,
             _processor_config_names_context_handle: processor_config_names_ctx_handle,
+            _replace_rules_context_handle: replace_rules_ctx_handle,
         }
     }
 
@@ -211,7 +226,11 @@ impl Component for InputOutputBox {
             }
             Msg::ProcessorConfigNamesContextUpdated(processor_config_names) => {
                 self.processor_config_names = processor_config_names;
-                self.processor = TextProcessor::with_config_names(self.processor_config_names.deref()).unwrap();
+                self.processor = TextProcessor::with_config_names(self.processor_config_names.deref()).unwrap().with_rules(self.replace_rules.deref());
+            }
+            Msg::ReplaceRulesContextUpdated(replace_rules) => {
+                self.replace_rules = replace_rules;
+                self.processor = TextProcessor::with_config_names(self.processor_config_names.deref()).unwrap().with_rules(self.replace_rules.deref());
             }
         }
         true
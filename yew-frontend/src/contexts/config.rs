@@ -1,21 +1,30 @@
 
 use text_cleaner::clean::Clean;
+use text_cleaner::language::Language;
+use text_cleaner::replace::ReplaceRule;
+use text_cleaner::stopwords::{StopwordOptions, Stopwords};
+use text_cleaner::transliterate::{TransliterationOptions, Transliterator};
 use anyhow::{anyhow, Result};
 use hashbrown::HashSet;
 use yew::{Reducible, UseReducerHandle};
 
+use super::ReplaceRules;
+
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct ProcessorConfigNames {
     pub functions: HashSet<String>,
-    pub size: usize
+    pub size: usize,
+    /// The language of the text, for the functions that depend on it.
+    pub language: Language,
 }
 
 impl Default for ProcessorConfigNames {
     fn default() -> Self {
         Self {
             functions: HashSet::from_iter(vec![String::from("trim")].into_iter()),
-            size: 1
+            size: 1,
+            language: Language::default(),
         }
     }
 }
@@ -25,7 +34,8 @@ impl ProcessorConfigNames {
     pub fn new() -> Self {
         Self {
             functions: HashSet::new(),
-            size: 0
+            size: 0,
+            language: Language::default(),
         }
     }
     pub fn all() -> Self {
@@ -44,8 +54,13 @@ impl ProcessorConfigNames {
                 String::from("remove_punctuation_marks"),
                 String::from("remove_letter_accents"),
                 String::from("normalize_unicode_characters"),
+                String::from("transliterate"),
+                String::from("html_to_text"),
+                String::from("remove_stopwords"),
+                String::from("lowercase"),
             ].into_iter()),
-            size: 13
+            size: 17,
+            language: Language::default(),
         }
     }
 
@@ -59,6 +74,14 @@ impl ProcessorConfigNames {
             self.size -= 1;
         }
     }
+
+    /// The function names, with the ones that parse the input
+    /// (like `html_to_text`) first so that the rest see plain text.
+    pub fn ordered(&self) -> Vec<&String> {
+        let mut functions = self.functions.iter().collect::<Vec<_>>();
+        functions.sort_by_key(|name| name.as_str() != "html_to_text");
+        functions
+    }
 }
 
 impl TryFrom<ProcessorConfigNames> for ProcessorConfig {
@@ -66,8 +89,9 @@ impl TryFrom<ProcessorConfigNames> for ProcessorConfig {
 
     fn try_from(config_names: ProcessorConfigNames) -> Result<Self, Self::Error> {
         let mut config = ProcessorConfig::new();
-        for func in config_names.functions {
-            match config.try_add(&func) {
+        config.language = config_names.language;
+        for func in config_names.ordered() {
+            match config.try_add(func) {
                 Ok(()) => {},
                 Err(_e) => {panic!();}
             }
@@ -81,8 +105,9 @@ impl TryFrom<&ProcessorConfigNames> for ProcessorConfig {
 
     fn try_from(config_names: &ProcessorConfigNames) -> Result<Self, Self::Error> {
         let mut config = ProcessorConfig::new();
-        for func in &config_names.functions {
-            match config.try_add(func.as_str()) {
+        config.language = config_names.language;
+        for func in config_names.ordered() {
+            match config.try_add(func) {
                 Ok(()) => {},
                 Err(_e) => {panic!();}
             }
@@ -103,16 +128,30 @@ pub fn toggle(hset: &HashSet<String>, key: &str) -> HashSet<String>
     hset
 }
 
+#[derive(Debug, Clone)]
+pub enum ConfigAction {
+    Toggle(String),
+    SetLanguage(Language),
+}
+
 impl Reducible for ProcessorConfigNames {
-    type Action = String;
+    type Action = ConfigAction;
     fn reduce(self: std::rc::Rc<Self>, action: Self::Action) -> std::rc::Rc<Self> {
-
-        let hset = toggle(&self.functions, &action);
-        let size = hset.len();
-        ProcessorConfigNames {
-            functions: hset,
-            size
-        }.into()
+        match action {
+            ConfigAction::Toggle(function_name) => {
+                let hset = toggle(&self.functions, &function_name);
+                let size = hset.len();
+                ProcessorConfigNames {
+                    functions: hset,
+                    size,
+                    language: self.language,
+                }.into()
+            },
+            ConfigAction::SetLanguage(language) => ProcessorConfigNames {
+                language,
+                ..(*self).clone()
+            }.into(),
+        }
     }
 }
 
@@ -122,14 +161,16 @@ pub type ProcessorConfigNamesContext = UseReducerHandle<ProcessorConfigNames>;
 
 pub struct ProcessorConfig {
     functions: Vec<Box<dyn Fn(String) -> String>>,
-    size: usize
+    size: usize,
+    language: Language,
 }
 
 impl ProcessorConfig {
     pub fn new() -> Self {
         Self {
             functions: vec![],
-            size: 0
+            size: 0,
+            language: Language::default(),
         }
     }
 
@@ -158,6 +199,16 @@ impl ProcessorConfig {
             "remove_punctuation_marks" => self.chain(|s| Clean::remove_punctuation_marks(&s)),
             "remove_letter_accents" => self.chain(|s| Clean::remove_letter_accents(&s)),
             "normalize_unicode_characters" => self.chain(|s| Clean::normalize_unicode_characters(&s)),
+            "html_to_text" => self.chain(|s| Clean::html_to_text(&s)),
+            "lowercase" => self.chain(|s| Clean::lowercase(&s)),
+            "transliterate" => {
+                let transliterator = Transliterator::new(&TransliterationOptions::default())?;
+                self.chain(move |s| transliterator.apply(&s))
+            },
+            "remove_stopwords" => {
+                let stopwords = Stopwords::new(&StopwordOptions { languages: vec![self.language], ..Default::default() })?;
+                self.chain(move |s| stopwords.remove(&s))
+            },
             _ => {
                 exists = false;
             }
@@ -168,6 +219,12 @@ impl ProcessorConfig {
             false => Err(anyhow!("Processor {} does not exist", name))
         }
     }
+
+    pub fn try_add_rule(&mut self, rule: &ReplaceRule) -> anyhow::Result<()> {
+        let rule = rule.compile()?;
+        self.chain(move |s| rule.apply(&s));
+        Ok(())
+    }
 }
 
 impl Default for ProcessorConfig {
@@ -191,6 +248,16 @@ impl TextProcessor {
             config: ProcessorConfig::try_from(config_names)?
         })
     }
+    /// Run the custom find/replace rules after the built-in functions.
+    /// Rules that are empty or don't compile are skipped.
+    pub fn with_rules(mut self, rules: &ReplaceRules) -> Self {
+        for rule in rules.rules.iter().filter(|rule| !rule.pattern.is_empty()) {
+            if let Err(e) = self.config.try_add_rule(rule) {
+                log::warn!("Skipping rule {:?}: {}", rule.pattern, e);
+            }
+        }
+        self
+    }
     pub fn process(&self, text: &str) -> String {
         let mut result = text.to_string();
         for f in &self.config.functions {
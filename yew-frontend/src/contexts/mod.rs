@@ -1,5 +1,7 @@
 mod text_input;
 mod config;
+mod rules;
 
 pub use text_input::*;
-pub use config::*;
\ No newline at end of file
+pub use config::*;
+pub use rules::*;
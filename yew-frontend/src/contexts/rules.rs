@@ -0,0 +1,44 @@
+use text_cleaner::replace::ReplaceRule;
+use yew::{Reducible, UseReducerHandle};
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ReplaceRules {
+    pub rules: Vec<ReplaceRule>,
+}
+
+impl ReplaceRules {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum RulesAction {
+    Add,
+    Update(usize, ReplaceRule),
+    Remove(usize),
+}
+
+impl Reducible for ReplaceRules {
+    type Action = RulesAction;
+
+    fn reduce(self: std::rc::Rc<Self>, action: Self::Action) -> std::rc::Rc<Self> {
+        let mut rules = self.rules.clone();
+        match action {
+            RulesAction::Add => rules.push(ReplaceRule::default()),
+            RulesAction::Update(idx, rule) => {
+                if let Some(existing) = rules.get_mut(idx) {
+                    *existing = rule;
+                }
+            },
+            RulesAction::Remove(idx) => {
+                if idx < rules.len() {
+                    rules.remove(idx);
+                }
+            }
+        }
+        Self { rules }.into()
+    }
+}
+
+pub type ReplaceRulesContext = UseReducerHandle<ReplaceRules>;